@@ -0,0 +1,113 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use super::*;
+use super::layout::TextPanels;
+
+/// Every exhibit in the museum, loaded from a `.catalog.ron` file
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "3f8e5b27-0c4d-4a19-b6e2-71d9a0c5e843"]
+pub struct Catalog {
+    exhibits: Vec<Exhibit>,
+}
+
+/// Metadata shown on an exhibit's tombstone
+#[derive(Deserialize)]
+pub struct Exhibit {
+    /// Name that the layout file refers to this exhibit by
+    pub id: String,
+    pub title: String,
+    pub date: String,
+    pub creator: String,
+    /// Description of the exhibit and its significance
+    pub summary: String,
+    /// MLA citation of the exhibit
+    pub source: String,
+    /// Name of the exhibit's texture in [GlobalResources::textures], needed to hang it on a wall
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Name of the exhibit's model in [GlobalResources::models], needed to show it on a pedestal
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Name of an audio track in [GlobalResources::audio] that plays alongside the exhibit
+    #[serde(default)]
+    pub audio: Option<String>,
+}
+
+impl Catalog {
+    /// Find the exhibit with the given id
+    pub fn get(&self, id: &str) -> Option<&Exhibit> {
+        self.exhibits.iter().find(|exhibit| exhibit.id == id)
+    }
+
+    /// Ids of every exhibit, each of which has a tombstone text panel once the catalog is spawned
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.exhibits.iter().map(|exhibit| exhibit.id.as_str())
+    }
+}
+
+/// Asset loader for `.catalog.ron` files
+#[derive(Default)]
+pub struct CatalogLoader;
+
+impl AssetLoader for CatalogLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let catalog: Catalog = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(catalog));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["catalog.ron"]
+    }
+}
+
+/// Marker component for tombstone text spawned from the catalog
+#[derive(Component)]
+pub struct ExhibitText;
+
+/// Spawn the tombstone text of every exhibit once the catalog has loaded, replacing the old text
+/// whenever the catalog file is modified
+pub fn spawn_catalog(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<Catalog>>,
+    catalogs: Res<Assets<Catalog>>,
+    resources: Res<GlobalResources>,
+    mut panels: ResMut<TextPanels>,
+    windows: Res<Windows>,
+    asset_server: Res<AssetServer>,
+    old_texts: Query<Entity, With<ExhibitText>>,
+) {
+    for event in events.iter() {
+        let handle = match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle,
+            AssetEvent::Removed { .. } => continue,
+        };
+        if *handle != resources.catalog {
+            continue
+        }
+
+        let old_texts = old_texts.iter().collect::<Vec<_>>();
+        panels.0.retain(|_, text| !old_texts.contains(text));
+        for text in old_texts {
+            commands.entity(text).despawn();
+        }
+
+        let font = asset_server.load("fonts/times-new-roman.ttf");
+        for exhibit in catalogs.get(handle).unwrap().exhibits.iter() {
+            let text = setup::tombstone(&mut commands, windows.primary(), font.clone(), exhibit);
+            commands.entity(text).insert(ExhibitText);
+            panels.0.insert(exhibit.id.clone(), text);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+
+/// Distance kept between a moving circle and the walls it stops against, so that the next sweep
+/// doesn't start touching the wall
+const SKIN: f32 = 0.001;
+/// Most times a movement is redirected along the walls it hits in one step
+const MAX_SLIDES: usize = 4;
+
+/// Collider component that specifies a wall has collision, as a line segment on the floor plan
+#[derive(Component, Clone, Copy, Debug)]
+pub struct LineCollider {
+    /// Start of the line segment
+    pub from: Vec2,
+    /// End of the line segment
+    pub to: Vec2,
+}
+
+impl LineCollider {
+    pub fn new(from: Vec2, to: Vec2) -> Self {
+        Self { from, to }
+    }
+
+    /// Point on the segment closest to the given point
+    pub fn closest(&self, point: Vec2) -> Vec2 {
+        let along = self.to - self.from;
+        let t = match along.length_squared() {
+            len if len > 0. => ((point - self.from).dot(along) / len).clamp(0., 1.),
+            _ => 0.,
+        };
+        self.from + along * t
+    }
+}
+
+/// Where a moving circle first touches a wall
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hit {
+    /// Fraction of the movement travelled before touching the wall
+    pub time: f32,
+    /// Direction from the wall to the circle where they touch
+    pub normal: Vec2,
+}
+
+/// Find where a circle moving from `position` by `movement` first touches a wall. The wall is
+/// widened into a capsule by the circle's radius, so its flat sides and rounded ends are checked
+/// against the circle's centre. Circles already touching the wall and moving into it hit at once
+pub fn sweep(position: Vec2, movement: Vec2, radius: f32, wall: &LineCollider) -> Option<Hit> {
+    let along = wall.to - wall.from;
+    let len = along.length();
+    let mut hit: Option<Hit> = None;
+    let mut consider = |time: f32, normal: Vec2| {
+        if (0. ..=1.).contains(&time) && hit.is_none_or(|hit| time < hit.time) {
+            hit = Some(Hit { time, normal });
+        }
+    };
+
+    // Flat sides, only where the centre is between the end points when it touches
+    if len > 0. {
+        let dir = along / len;
+        let mut normal = dir.perp();
+        let mut distance = (position - wall.from).dot(normal);
+        if distance < 0. {
+            normal = -normal;
+            distance = -distance;
+        }
+        let approach = -movement.dot(normal);
+        if approach > 0. {
+            let time = ((distance - radius) / approach).max(0.);
+            let along = (position + movement * time - wall.from).dot(dir);
+            if (0. ..=len).contains(&along) {
+                consider(time, normal);
+            }
+        }
+    }
+
+    // Rounded ends
+    for end in [wall.from, wall.to] {
+        let offset = position - end;
+        let b = offset.dot(movement);
+        if b >= 0. {
+            continue
+        }
+        let c = offset.length_squared() - radius * radius;
+        if c <= 0. {
+            consider(0., offset.normalize_or_zero());
+            continue
+        }
+        let a = movement.length_squared();
+        let discriminant = b * b - a * c;
+        if discriminant >= 0. {
+            let time = (-b - discriminant.sqrt()) / a;
+            consider(time, (offset + movement * time).normalize_or_zero());
+        }
+    }
+
+    hit
+}
+
+/// Push a circle out of any walls it overlaps
+pub fn depenetrate(mut position: Vec2, radius: f32, walls: &[LineCollider]) -> Vec2 {
+    for wall in walls {
+        let away = position - wall.closest(position);
+        let distance = away.length();
+        if distance < radius {
+            let normal = match distance > 0. {
+                true => away / distance,
+                false => (wall.to - wall.from).perp().normalize_or_zero(),
+            };
+            position += normal * (radius - distance + SKIN);
+        }
+    }
+    position
+}
+
+/// Move a circle by `movement`, stopping against the walls it hits and sliding along them with
+/// the rest of the movement. Fast movements can't pass through walls, and corners and wall ends
+/// stop the circle the same way as the middle of a wall
+pub fn slide(position: Vec2, mut movement: Vec2, radius: f32, walls: &[LineCollider]) -> Vec2 {
+    let mut position = depenetrate(position, radius, walls);
+    for _ in 0..MAX_SLIDES {
+        let length = movement.length();
+        if length <= SKIN {
+            break
+        }
+
+        let hit = walls
+            .iter()
+            .filter_map(|wall| sweep(position, movement, radius, wall))
+            .min_by(|a, b| a.time.total_cmp(&b.time));
+        let hit = match hit {
+            Some(hit) => hit,
+            None => return position + movement,
+        };
+
+        // Stop against the wall with a gap, then slide the rest of the movement along it
+        position += movement * hit.time + hit.normal * SKIN;
+        let remaining = movement * (1. - hit.time);
+        movement = remaining - hit.normal * remaining.dot(hit.normal).min(0.);
+    }
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RADIUS: f32 = 0.5;
+
+    fn wall(from: (f32, f32), to: (f32, f32)) -> LineCollider {
+        LineCollider::new(from.into(), to.into())
+    }
+
+    /// Distance from the circle's centre to the nearest wall
+    fn clearance(position: Vec2, walls: &[LineCollider]) -> f32 {
+        walls
+            .iter()
+            .map(|wall| wall.closest(position).distance(position))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    #[test]
+    fn free_movement_is_unchanged() {
+        let walls = [wall((0., 5.), (10., 5.))];
+        let end = slide(Vec2::new(1., 1.), Vec2::new(2., 1.), RADIUS, &walls);
+        assert!(end.distance(Vec2::new(3., 2.)) < 1e-5);
+    }
+
+    #[test]
+    fn stops_against_wall() {
+        let walls = [wall((-5., 2.), (5., 2.))];
+        let end = slide(Vec2::ZERO, Vec2::new(0., 3.), RADIUS, &walls);
+        assert!((end.y - 1.5).abs() < 0.01, "{}", end);
+        assert!(clearance(end, &walls) >= RADIUS);
+    }
+
+    #[test]
+    fn slides_along_wall() {
+        let walls = [wall((-5., 1.), (5., 1.))];
+        let end = slide(Vec2::new(0., 0.4), Vec2::new(1., 1.), RADIUS, &walls);
+        assert!((end.x - 1.).abs() < 0.01, "{}", end);
+        assert!(end.y < 0.5 && clearance(end, &walls) >= RADIUS, "{}", end);
+    }
+
+    #[test]
+    fn fast_movement_does_not_tunnel() {
+        let walls = [wall((-5., 2.), (5., 2.))];
+        let end = slide(Vec2::ZERO, Vec2::new(0., 50.), RADIUS, &walls);
+        assert!(end.y < 2., "{}", end);
+    }
+
+    #[test]
+    fn wall_ends_are_solid() {
+        // Moving straight at the end of a wall, which the old projection missed
+        let walls = [wall((0., 0.), (0., 5.))];
+        let end = slide(Vec2::new(0., -2.), Vec2::new(0., 3.), RADIUS, &walls);
+        assert!(clearance(end, &walls) >= RADIUS, "{}", end);
+
+        // Grazing the end slides around it instead of through it
+        let end = slide(Vec2::new(0.3, -2.), Vec2::new(0., 3.), RADIUS, &walls);
+        assert!(clearance(end, &walls) >= RADIUS, "{}", end);
+        assert!(end.x > 0.3, "{}", end);
+    }
+
+    #[test]
+    fn corners_do_not_leak() {
+        let walls = [wall((0., 0.), (4., 0.)), wall((0., 0.), (0., 4.))];
+        let mut position = Vec2::new(2., 2.);
+        for _ in 0..20 {
+            position = slide(position, Vec2::new(-0.3, -0.3), RADIUS, &walls);
+            assert!(clearance(position, &walls) >= RADIUS, "{}", position);
+        }
+        assert!(position.distance(Vec2::splat(RADIUS)) < 0.01, "{}", position);
+    }
+
+    #[test]
+    fn corridors_do_not_jitter() {
+        // A corridor barely wider than the player, walked along while pushing into one side
+        let walls = [wall((0., -0.55), (10., -0.55)), wall((10., 0.55), (0., 0.55))];
+        let mut position = Vec2::ZERO;
+        let mut last_y: Option<f32> = None;
+        for _ in 0..20 {
+            position = slide(position, Vec2::new(0.2, 0.1), RADIUS, &walls);
+            assert!(clearance(position, &walls) >= RADIUS, "{}", position);
+            if let Some(y) = last_y {
+                assert!((position.y - y).abs() < 1e-4, "{}", position);
+            }
+            last_y = Some(position.y);
+        }
+        assert!((position.x - 4.).abs() < 0.01, "{}", position);
+    }
+
+    #[test]
+    fn overlapping_circles_are_pushed_out() {
+        let walls = [wall((-5., 0.), (5., 0.))];
+        let end = depenetrate(Vec2::new(0., 0.2), RADIUS, &walls);
+        assert!(end.y >= RADIUS, "{}", end);
+    }
+
+    #[test]
+    fn moving_away_from_touching_wall_is_free() {
+        let walls = [wall((-5., 0.), (5., 0.))];
+        let start = Vec2::new(0., RADIUS + SKIN);
+        let end = slide(start, Vec2::new(0., 1.), RADIUS, &walls);
+        assert!(end.distance(start + Vec2::Y) < 1e-5, "{}", end);
+    }
+}
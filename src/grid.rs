@@ -0,0 +1,85 @@
+use super::*;
+
+/// Width and depth of each cell of the [SpatialGrid]
+const CELL_SIZE: f32 = 4.;
+
+/// Colliders and interactables sorted into square cells on the floor plan, so that the ones near
+/// the player can be found without going through every one in the museum. Rebuilt by
+/// [update_grid] whenever any of them change
+#[derive(Default)]
+pub struct SpatialGrid {
+    /// Every collider in the museum
+    colliders: Vec<LineCollider>,
+    /// Indices into `colliders` of the colliders crossing the bounds of each cell
+    collider_cells: HashMap<IVec2, Vec<usize>>,
+    /// Interactables whose radius reaches into each cell
+    interactable_cells: HashMap<IVec2, Vec<Entity>>,
+}
+
+impl SpatialGrid {
+    /// Cell that a point is in
+    fn cell(point: Vec2) -> IVec2 {
+        (point / CELL_SIZE).floor().as_ivec2()
+    }
+
+    /// Every cell overlapping the rectangle between two corners
+    fn cells(min: Vec2, max: Vec2) -> impl Iterator<Item = IVec2> {
+        let (min, max) = (Self::cell(min), Self::cell(max));
+        (min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+    }
+
+    /// Colliders that may cross the rectangle between two corners, each listed once
+    pub fn colliders_in(&self, min: Vec2, max: Vec2) -> Vec<LineCollider> {
+        let mut indices = Self::cells(min, max)
+            .filter_map(|cell| self.collider_cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|idx| self.colliders[idx]).collect()
+    }
+
+    /// Interactables whose radius may reach the given point
+    pub fn interactables_at(&self, point: Vec2) -> &[Entity] {
+        self.interactable_cells
+            .get(&Self::cell(point))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Rebuild the [SpatialGrid] when colliders or interactables are added, changed or removed. Runs
+/// after the update stage, once the layout's commands have been applied and before removals are
+/// cleared at the end of the frame
+pub fn update_grid(
+    mut grid: ResMut<SpatialGrid>,
+    changed: Query<(), Or<(Changed<LineCollider>, Changed<Interactable>)>>,
+    removed_colliders: RemovedComponents<LineCollider>,
+    removed_interactables: RemovedComponents<Interactable>,
+    colliders: Query<&LineCollider>,
+    interactables: Query<(Entity, &Interactable)>,
+) {
+    if changed.is_empty()
+        && removed_colliders.iter().next().is_none()
+        && removed_interactables.iter().next().is_none()
+    {
+        return
+    }
+
+    let mut rebuilt = SpatialGrid {
+        colliders: colliders.iter().copied().collect(),
+        ..default()
+    };
+    for (idx, collider) in rebuilt.colliders.iter().enumerate() {
+        for cell in SpatialGrid::cells(collider.from.min(collider.to), collider.from.max(collider.to)) {
+            rebuilt.collider_cells.entry(cell).or_default().push(idx);
+        }
+    }
+    for (entity, interactable) in interactables.iter() {
+        let reach = Vec2::splat(interactable.radius);
+        for cell in SpatialGrid::cells(interactable.point - reach, interactable.point + reach) {
+            rebuilt.interactable_cells.entry(cell).or_default().push(entity);
+        }
+    }
+    *grid = rebuilt;
+}
@@ -0,0 +1,1007 @@
+use std::{collections::BTreeMap, fmt};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    reflect::TypeUuid,
+    utils::{BoxedFuture, HashMap},
+};
+use serde::{de::{MapAccess, Visitor}, Deserialize, Deserializer};
+
+use super::*;
+use super::scene::*;
+use super::plan::*;
+use super::catalog::Catalog;
+
+/// Declarative description of the museum's walls and floors, loaded from a `.layout.ron` file
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "9a4d2c1e-6f3b-4b8e-a5d7-3e1c0f2b8a64"]
+pub struct Layout {
+    /// Named points that walls and floors are placed between, in the order they are defined
+    #[serde(deserialize_with = "ordered_map")]
+    vertices: Vec<(String, (f32, f32))>,
+    /// All walls in the museum
+    #[serde(default)]
+    walls: Vec<WallDesc>,
+    /// All floors and ceilings in the museum
+    #[serde(default)]
+    floors: Vec<FloorDesc>,
+    /// All rooms in the museum
+    #[serde(default)]
+    rooms: Vec<RoomDesc>,
+    /// All exhibits in the museum
+    #[serde(default)]
+    exhibits: Vec<ExhibitDesc>,
+    /// All pedestals and display cases in the museum
+    #[serde(default)]
+    boxes: Vec<BoxDesc>,
+    /// All exhibits shown as models on pedestals
+    #[serde(default)]
+    artifacts: Vec<ArtifactDesc>,
+    /// Source of the layout file, used to find the line of elements in error messages
+    #[serde(skip)]
+    source: String,
+}
+
+/// Deserialize a map into a list of its entries, keeping entries with duplicate keys so that the
+/// floor plan can report them
+fn ordered_map<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(String, (f32, f32))>, D::Error> {
+    struct EntriesVisitor;
+
+    impl<'de> Visitor<'de> for EntriesVisitor {
+        type Value = Vec<(String, (f32, f32))>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a map of vertex names to positions")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+            while let Some(entry) = map.next_entry()? {
+                entries.push(entry);
+            }
+            Ok(entries)
+        }
+    }
+
+    deserializer.deserialize_map(EntriesVisitor)
+}
+
+/// How many times a texture is repeated over a wall or floor
+#[derive(Deserialize, Clone, Copy)]
+pub enum Tiling {
+    /// Repeat the texture a fixed amount of times in the X and Y coordinates
+    Tiles(f32, f32),
+    /// Calculate repetitions from the size of the wall or floor
+    Auto,
+    /// Calculate repetitions from the length of a wall, using one tile for its height
+    AutoLength,
+    /// Repeat the texture once every given number of metres in the X and Y coordinates, lined up
+    /// in world space so that it continues across neighbouring walls and floors
+    Size(f32, f32),
+}
+
+/// Side of a wall or floor to cull while rendering
+#[derive(Deserialize, Clone, Copy)]
+pub enum CullFace {
+    Front,
+    Back,
+}
+
+impl From<CullFace> for Face {
+    fn from(cull: CullFace) -> Self {
+        match cull {
+            CullFace::Front => Face::Front,
+            CullFace::Back => Face::Back,
+        }
+    }
+}
+
+/// Face of a wall that a decal is placed on
+#[derive(Deserialize, Clone, Copy)]
+pub enum Side {
+    Front,
+    Back,
+}
+
+impl From<Side> for WallSide {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Front => WallSide::Front,
+            Side::Back => WallSide::Back,
+        }
+    }
+}
+
+/// Face of a box, named after the axis it faces along
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BoxFaceDesc {
+    Top,
+    Bottom,
+    PosX,
+    NegX,
+    PosZ,
+    NegZ,
+}
+
+impl From<BoxFaceDesc> for BoxFace {
+    fn from(face: BoxFaceDesc) -> Self {
+        match face {
+            BoxFaceDesc::Top => BoxFace::Top,
+            BoxFaceDesc::Bottom => BoxFace::Bottom,
+            BoxFaceDesc::PosX => BoxFace::PosX,
+            BoxFaceDesc::NegX => BoxFace::NegX,
+            BoxFaceDesc::PosZ => BoxFace::PosZ,
+            BoxFaceDesc::NegZ => BoxFace::NegZ,
+        }
+    }
+}
+
+/// Action taken when interacting with a wall, referencing text panels, exhibits and audio by name
+#[derive(Deserialize)]
+pub enum ActionDesc {
+    Tombstone {
+        text: String,
+        name: String,
+        #[serde(default)]
+        view: Option<ViewDesc>,
+    },
+    /// Read the tombstone of an exhibit in the catalog
+    Exhibit(String),
+    Audio(String),
+    /// View a texture full-screen, using its high-resolution version if there is one
+    Image {
+        texture: String,
+        name: String,
+    },
+    Tooltip(String),
+}
+
+/// Camera pose used while reading a tombstone, see [ViewPose]
+#[derive(Deserialize)]
+pub struct ViewDesc {
+    eye: (f32, f32, f32),
+    target: (f32, f32, f32),
+}
+
+impl From<&ViewDesc> for ViewPose {
+    fn from(desc: &ViewDesc) -> Self {
+        ViewPose {
+            eye: desc.eye.into(),
+            target: desc.target.into(),
+        }
+    }
+}
+
+/// Parameters of a [WallBuilder], see the builder for what each one does
+#[derive(Deserialize)]
+pub struct WallDesc {
+    /// Name used to identify this wall in error messages
+    name: String,
+    from: Point,
+    to: Point,
+    #[serde(default)]
+    texture: Option<String>,
+    #[serde(default)]
+    color: Option<(f32, f32, f32)>,
+    #[serde(default)]
+    height: Option<f32>,
+    #[serde(default)]
+    offset: Option<f32>,
+    #[serde(default)]
+    tiling: Option<Tiling>,
+    #[serde(default)]
+    mirror: Option<bool>,
+    #[serde(default)]
+    cull: Option<CullFace>,
+    #[serde(default)]
+    collision: Option<bool>,
+    #[serde(default)]
+    transparent: Option<bool>,
+    #[serde(default)]
+    action: Option<ActionDesc>,
+    #[serde(default)]
+    radius: Option<f32>,
+    /// Doorways as `(start, width, height)`
+    #[serde(default)]
+    openings: Vec<(f32, f32, f32)>,
+    #[serde(default)]
+    thickness: Option<f32>,
+    #[serde(default)]
+    decals: Vec<DecalDesc>,
+}
+
+/// Parameters of a [DecalBuilder], see the builder for what each one does
+#[derive(Deserialize)]
+pub struct DecalDesc {
+    start: f32,
+    width: f32,
+    height: f32,
+    #[serde(default)]
+    side: Option<Side>,
+    #[serde(default)]
+    offset: Option<f32>,
+    #[serde(default)]
+    texture: Option<String>,
+    /// Only `Tiles` is supported on decals
+    #[serde(default)]
+    tiling: Option<Tiling>,
+    #[serde(default)]
+    mirror: Option<bool>,
+    #[serde(default)]
+    transparent: Option<bool>,
+    #[serde(default)]
+    action: Option<ActionDesc>,
+}
+
+/// Parameters of a [FloorBuilder], see the builder for what each one does. Floors are either a
+/// rectangle between `from` and `to`, or a `polygon` with optional `holes`
+#[derive(Deserialize)]
+pub struct FloorDesc {
+    /// Name used to identify this floor in error messages
+    name: String,
+    #[serde(default)]
+    from: Option<Point>,
+    #[serde(default)]
+    to: Option<Point>,
+    #[serde(default)]
+    polygon: Vec<Point>,
+    #[serde(default)]
+    holes: Vec<Vec<Point>>,
+    #[serde(default)]
+    texture: Option<String>,
+    #[serde(default)]
+    color: Option<(f32, f32, f32)>,
+    #[serde(default)]
+    brightness: Option<f32>,
+    #[serde(default)]
+    offset: Option<f32>,
+    #[serde(default)]
+    tiling: Option<Tiling>,
+    #[serde(default)]
+    mirror: Option<bool>,
+    #[serde(default)]
+    cull: Option<CullFace>,
+}
+
+/// Parameters of a [RoomBuilder], see the builder for what each one does
+#[derive(Deserialize)]
+pub struct RoomDesc {
+    /// Name used to identify this room in error messages
+    name: String,
+    polygon: Vec<Point>,
+    #[serde(default)]
+    height: Option<f32>,
+    #[serde(default)]
+    wall_texture: Option<String>,
+    /// Textures of individual walls, keyed by the index of the corner the wall begins at
+    #[serde(default)]
+    side_textures: BTreeMap<usize, String>,
+    #[serde(default)]
+    floor_texture: Option<String>,
+    #[serde(default)]
+    ceiling_texture: Option<String>,
+    /// Only `Size` is supported on rooms, and applies to their walls. Their floor and ceiling use
+    /// the width along both axes
+    #[serde(default)]
+    tiling: Option<Tiling>,
+    /// Doorways as `(side, start, width, height)`
+    #[serde(default)]
+    openings: Vec<(usize, f32, f32, f32)>,
+}
+
+/// Parameters of an [ExhibitBuilder], which takes its artwork, tombstone and audio from the
+/// exhibit with the same id in the [Catalog]
+#[derive(Deserialize)]
+pub struct ExhibitDesc {
+    /// Id of the exhibit in the catalog, also used to identify this exhibit in error messages
+    id: String,
+    /// First end point of the wall the exhibit hangs on, it faces the right of the wall's direction
+    from: Point,
+    to: Point,
+    /// Distance along the wall from `from` to the start of the artwork
+    start: f32,
+    /// Width and height of the artwork
+    size: (f32, f32),
+    #[serde(default)]
+    transparent: Option<bool>,
+    /// Whether reading the placard looks at the artwork close up instead of over a backdrop
+    #[serde(default)]
+    close_up: bool,
+}
+
+/// Parameters of a [BoxBuilder], see the builder for what each one does
+#[derive(Deserialize)]
+pub struct BoxDesc {
+    /// Name used to identify this box in error messages
+    name: String,
+    /// Centre of the bottom of the box
+    position: Point,
+    /// Width along X, height, and depth along Z
+    size: (f32, f32, f32),
+    #[serde(default)]
+    offset: Option<f32>,
+    #[serde(default)]
+    texture: Option<String>,
+    /// Textures of individual faces, replacing the box's texture
+    #[serde(default)]
+    face_textures: BTreeMap<BoxFaceDesc, String>,
+    /// Decals on the sides of the box, their `side` is ignored
+    #[serde(default)]
+    decals: Vec<(BoxFaceDesc, DecalDesc)>,
+    #[serde(default)]
+    collision: Option<bool>,
+    /// Build a glass case with a transparent top instead of a solid box
+    #[serde(default)]
+    glass: bool,
+    #[serde(default)]
+    action: Option<ActionDesc>,
+    #[serde(default)]
+    radius: Option<f32>,
+}
+
+/// Parameters of an [ArtifactBuilder], which takes its model and tombstone from the exhibit with
+/// the same id in the [Catalog]
+#[derive(Deserialize)]
+pub struct ArtifactDesc {
+    /// Id of the exhibit in the catalog, also used to identify this artifact in error messages
+    id: String,
+    /// Centre of the pedestal
+    position: Point,
+    /// Width, height and depth of the pedestal
+    #[serde(default)]
+    pedestal: Option<(f32, f32, f32)>,
+    /// Texture of the pedestal
+    #[serde(default)]
+    texture: Option<String>,
+    #[serde(default)]
+    scale: Option<f32>,
+    /// Rotation of the model around the vertical axis in degrees
+    #[serde(default)]
+    rotation: Option<f32>,
+    #[serde(default)]
+    radius: Option<f32>,
+}
+
+/// Error produced while parsing a layout file or turning it into a [SceneBuilder]
+#[derive(Debug)]
+pub struct LayoutError {
+    /// Name of the wall or floor that caused the error, if it could be determined
+    element: Option<String>,
+    /// Line of the layout file that the element is defined on
+    line: usize,
+    kind: LayoutErrorKind,
+}
+
+#[derive(Debug)]
+pub enum LayoutErrorKind {
+    /// The file is not valid RON or doesn't match the layout format
+    Syntax(ron::error::ErrorCode),
+    /// The vertex table has duplicates, or a wall or floor has an invalid position
+    Plan(PlanError),
+    /// A texture name that is not in [GlobalResources::textures]
+    UnknownTexture(String),
+    /// An audio name that is not in [GlobalResources::audio]
+    UnknownAudio(String),
+    /// A text panel name that is not in [TextPanels]
+    UnknownText(String),
+    /// An exhibit that is not in the [Catalog]
+    UnknownExhibit(String),
+    /// A model name that is not in [GlobalResources::models]
+    UnknownModel(String),
+    /// An exhibit hung on a wall that has no image in the [Catalog]
+    NoImage,
+    /// An artifact that has no model in the [Catalog]
+    NoModel,
+    /// Tiling mode that can't be applied to this kind of element
+    InvalidTiling,
+    /// A floor that isn't either a rectangle or a polygon
+    InvalidShape,
+    /// A side of a room that is past its last corner
+    UnknownSide(usize),
+    /// A decal on the top or bottom of a box
+    InvalidDecal,
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.element {
+            Some(element) => write!(f, "'{}' (line {}): ", element, self.line)?,
+            None => write!(f, "line {}: ", self.line)?,
+        }
+        match &self.kind {
+            LayoutErrorKind::Syntax(code) => write!(f, "{}", code),
+            LayoutErrorKind::Plan(e) => write!(f, "{}", e),
+            LayoutErrorKind::UnknownTexture(name) => write!(f, "unknown texture '{}'", name),
+            LayoutErrorKind::UnknownAudio(name) => write!(f, "unknown audio track '{}'", name),
+            LayoutErrorKind::UnknownText(name) => write!(f, "unknown text panel '{}'", name),
+            LayoutErrorKind::UnknownExhibit(name) => write!(f, "unknown exhibit '{}'", name),
+            LayoutErrorKind::UnknownModel(name) => write!(f, "unknown model '{}'", name),
+            LayoutErrorKind::NoImage => write!(f, "exhibit has no image in the catalog"),
+            LayoutErrorKind::NoModel => write!(f, "exhibit has no model in the catalog"),
+            LayoutErrorKind::InvalidTiling => write!(f, "tiling mode is not supported on this element"),
+            LayoutErrorKind::InvalidShape => {
+                write!(f, "floors need either `from` and `to`, or a `polygon` with optional `holes`")
+            },
+            LayoutErrorKind::UnknownSide(side) => write!(f, "room has no side {}", side),
+            LayoutErrorKind::InvalidDecal => write!(f, "decals can only be placed on the sides of boxes"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// Text entities that can be shown by a tombstone action, keyed by the name used in the layout
+/// file or the exhibit's id in the catalog
+#[derive(Default)]
+pub struct TextPanels(pub HashMap<String, Entity>);
+
+impl Layout {
+    /// Parse a layout from the source of a layout file
+    pub fn parse(source: &str) -> Result<Self, LayoutError> {
+        let mut layout: Self = ron::de::from_str(source).map_err(|e| LayoutError {
+            element: element_before(source, e.position.line),
+            line: e.position.line,
+            kind: LayoutErrorKind::Syntax(e.code),
+        })?;
+        layout.source = source.to_owned();
+        Ok(layout)
+    }
+
+    /// Create a scene containing every wall, floor, room, exhibit, box and artifact in this layout, placed on a floor plan
+    /// made from the layout's vertex table
+    pub fn scene(
+        &self,
+        resources: &GlobalResources,
+        panels: &TextPanels,
+        catalog: &Catalog,
+    ) -> Result<SceneBuilder, LayoutError> {
+        let plan = self.vertices
+            .iter()
+            .fold(FloorPlan::new(), |plan, (name, pos)| plan.with_vertex(name.as_str(), *pos));
+
+        let mut scene = SceneBuilder::new(plan);
+        for wall in self.walls.iter() {
+            scene = scene.with_wall(self.wall(wall, resources, panels, catalog)?);
+        }
+        for floor in self.floors.iter() {
+            scene = scene.with_floor(self.floor(floor, resources)?);
+        }
+        for room in self.rooms.iter() {
+            scene = scene.with_room(self.room(room, resources)?);
+        }
+        for exhibit in self.exhibits.iter() {
+            scene = scene.with_exhibit(self.exhibit(exhibit, resources, panels, catalog)?);
+        }
+        for bx in self.boxes.iter() {
+            scene = scene.with_box(self.bx(bx, resources, panels, catalog)?);
+        }
+        for artifact in self.artifacts.iter() {
+            scene = scene.with_artifact(self.artifact(artifact, resources, catalog)?);
+        }
+        Ok(scene)
+    }
+
+    /// Check that every name this layout refers to can be resolved and that its floor plan is
+    /// valid, without adding anything to the world
+    pub fn check(
+        &self,
+        resources: &GlobalResources,
+        panels: &TextPanels,
+        catalog: &Catalog,
+    ) -> Result<(), LayoutError> {
+        self.scene(resources, panels, catalog)?
+            .validate()
+            .map_err(|e| self.plan_error(e))
+    }
+
+    /// Attach the line of the offending vertex or element to an error from the floor plan
+    pub fn plan_error(&self, error: PlanError) -> LayoutError {
+        let line = match &error {
+            PlanError::DuplicateVertex(name) => {
+                let key = format!("\"{}\":", name);
+                self.source
+                    .lines()
+                    .enumerate()
+                    .filter(|(_, line)| line.trim_start().starts_with(&key))
+                    .nth(1)
+                    .map_or(0, |(idx, _)| idx + 1)
+            },
+            PlanError::UnknownVertex { element, .. }
+            | PlanError::ZeroLength(element)
+            | PlanError::NoArea(element)
+            | PlanError::TooFewPoints(element) => {
+                // Walls of a room are named after the room and their side
+                let room = element.rsplit_once(" side ").map_or(element.as_str(), |(room, _)| room);
+                match line_of(&self.source, element) {
+                    0 => line_of(&self.source, room),
+                    line => line,
+                }
+            },
+        };
+
+        // The element's name is already part of the plan error's message
+        LayoutError { element: None, line, kind: LayoutErrorKind::Plan(error) }
+    }
+
+    fn wall(
+        &self,
+        desc: &WallDesc,
+        resources: &GlobalResources,
+        panels: &TextPanels,
+        catalog: &Catalog,
+    ) -> Result<WallBuilder, LayoutError> {
+        let err = |kind| LayoutError {
+            element: Some(desc.name.clone()),
+            line: line_of(&self.source, &desc.name),
+            kind,
+        };
+
+        let mut wall = WallBuilder::new(desc.from.clone(), desc.to.clone())
+            .with_name(desc.name.as_str());
+
+        if let Some(height) = desc.height {
+            wall = wall.with_height(height);
+        }
+        if let Some(offset) = desc.offset {
+            wall = wall.with_offset(offset);
+        }
+        if let Some(texture) = &desc.texture {
+            wall = wall.with_texture(texture_handle(resources, texture).map_err(err)?);
+        }
+        if let Some((r, g, b)) = desc.color {
+            wall = wall.with_color(Color::rgb(r, g, b));
+        }
+        wall = match desc.tiling {
+            Some(Tiling::Tiles(wide, tall)) => wall.with_tiles(wide, tall),
+            Some(Tiling::Auto) => wall.autotile(),
+            Some(Tiling::AutoLength) => wall.autotile_len(),
+            Some(Tiling::Size(width, height)) => wall.with_texture_size(width, height),
+            None => wall,
+        };
+        if let Some(mirror) = desc.mirror {
+            wall = wall.with_mirror(mirror);
+        }
+        if let Some(cull) = desc.cull {
+            wall = wall.with_cull(cull.into());
+        }
+        if let Some(collision) = desc.collision {
+            wall = wall.with_collision(collision);
+        }
+        if let Some(transparent) = desc.transparent {
+            wall = wall.with_transparency(transparent);
+        }
+        if let Some(radius) = desc.radius {
+            wall = wall.with_radius(radius);
+        }
+        for &(start, width, height) in desc.openings.iter() {
+            wall = wall.with_opening(start, width, height);
+        }
+        if let Some(thickness) = desc.thickness {
+            wall = wall.with_thickness(thickness);
+        }
+        if let Some(action) = &desc.action {
+            wall = wall.with_action(action_of(action, resources, panels, catalog).map_err(err)?);
+        }
+        for decal in desc.decals.iter() {
+            wall = wall.with_decal(decal_of(decal, resources, panels, catalog).map_err(err)?);
+        }
+
+        Ok(wall)
+    }
+
+    fn floor(&self, desc: &FloorDesc, resources: &GlobalResources) -> Result<FloorBuilder, LayoutError> {
+        let err = |kind| LayoutError {
+            element: Some(desc.name.clone()),
+            line: line_of(&self.source, &desc.name),
+            kind,
+        };
+
+        let polygon = !desc.polygon.is_empty();
+        let mut floor = match (&desc.from, &desc.to) {
+            (Some(from), Some(to)) if !polygon && desc.holes.is_empty() => {
+                FloorBuilder::new(from.clone(), to.clone())
+            },
+            (None, None) if polygon => desc.holes
+                .iter()
+                .fold(FloorBuilder::polygon(desc.polygon.iter().cloned()), |floor, hole| {
+                    floor.with_hole(hole.iter().cloned())
+                }),
+            _ => return Err(err(LayoutErrorKind::InvalidShape)),
+        };
+        floor = floor.with_name(desc.name.as_str());
+
+        if let Some(offset) = desc.offset {
+            floor = floor.with_offset(offset);
+        }
+        if let Some(texture) = &desc.texture {
+            floor = floor.with_texture(texture_handle(resources, texture).map_err(err)?);
+        }
+        if let Some(brightness) = desc.brightness {
+            floor = floor.with_brightness(brightness);
+        }
+        if let Some((r, g, b)) = desc.color {
+            floor = floor.with_color(Color::rgb(r, g, b));
+        }
+        // Polygon floors are always textured in world space
+        floor = match desc.tiling {
+            Some(Tiling::Tiles(wide, tall)) if !polygon => floor.with_tiles(wide, tall),
+            Some(Tiling::Auto) => floor.autotile(),
+            Some(Tiling::Size(width, height)) => floor.with_texture_size(width, height),
+            Some(_) => return Err(err(LayoutErrorKind::InvalidTiling)),
+            None => floor,
+        };
+        if let Some(mirror) = desc.mirror {
+            floor = floor.with_mirror(mirror);
+        }
+        if let Some(cull) = desc.cull {
+            floor = floor.with_cull(cull.into());
+        }
+
+        Ok(floor)
+    }
+
+    fn room(&self, desc: &RoomDesc, resources: &GlobalResources) -> Result<RoomBuilder, LayoutError> {
+        let err = |kind| LayoutError {
+            element: Some(desc.name.clone()),
+            line: line_of(&self.source, &desc.name),
+            kind,
+        };
+
+        let mut room = RoomBuilder::new(desc.polygon.iter().cloned())
+            .with_name(desc.name.as_str());
+
+        if let Some(height) = desc.height {
+            room = room.with_height(height);
+        }
+        if let Some(texture) = &desc.wall_texture {
+            room = room.with_wall_texture(texture_handle(resources, texture).map_err(err)?);
+        }
+        for (&side, texture) in desc.side_textures.iter() {
+            if side >= room.sides() {
+                return Err(err(LayoutErrorKind::UnknownSide(side)));
+            }
+            room = room.with_side_texture(side, texture_handle(resources, texture).map_err(err)?);
+        }
+        for &(side, start, width, height) in desc.openings.iter() {
+            if side >= room.sides() {
+                return Err(err(LayoutErrorKind::UnknownSide(side)));
+            }
+            room = room.with_side_opening(side, start, width, height);
+        }
+        if let Some(texture) = &desc.floor_texture {
+            room = room.with_floor_texture(texture_handle(resources, texture).map_err(err)?);
+        }
+        if let Some(texture) = &desc.ceiling_texture {
+            room = room.with_ceiling_texture(texture_handle(resources, texture).map_err(err)?);
+        }
+        room = match desc.tiling {
+            Some(Tiling::Size(width, height)) => room.with_texture_size(width, height),
+            Some(_) => return Err(err(LayoutErrorKind::InvalidTiling)),
+            None => room,
+        };
+
+        Ok(room)
+    }
+
+    fn exhibit(
+        &self,
+        desc: &ExhibitDesc,
+        resources: &GlobalResources,
+        panels: &TextPanels,
+        catalog: &Catalog,
+    ) -> Result<ExhibitBuilder, LayoutError> {
+        let err = |kind| LayoutError {
+            element: Some(desc.id.clone()),
+            line: line_of(&self.source, &desc.id),
+            kind,
+        };
+        let unknown = || err(LayoutErrorKind::UnknownExhibit(desc.id.clone()));
+        let entry = catalog.get(&desc.id).ok_or_else(unknown)?;
+
+        let (width, height) = desc.size;
+        let image = entry.image.as_ref().ok_or_else(|| err(LayoutErrorKind::NoImage))?;
+        let image = texture_handle(resources, image).map_err(err)?;
+        let mut exhibit = ExhibitBuilder::new(desc.from.clone(), desc.to.clone(), desc.start, Vec2::new(width, height), image)
+            .with_name(desc.id.as_str())
+            .with_placard(
+                texture_handle(resources, "tombstone").map_err(err)?,
+                InteractableAction::Tombstone {
+                    text: *panels.0.get(&desc.id).ok_or_else(unknown)?,
+                    name: entry.title.clone(),
+                    view: None,
+                },
+            );
+
+        if let Some(transparent) = desc.transparent {
+            exhibit = exhibit.with_transparency(transparent);
+        }
+        if desc.close_up {
+            exhibit = exhibit.with_close_up();
+        }
+        if let Some(audio) = &entry.audio {
+            let source = resources.audio
+                .get(audio.as_str())
+                .cloned()
+                .ok_or_else(|| err(LayoutErrorKind::UnknownAudio(audio.clone())))?;
+            exhibit = exhibit.with_audio(texture_handle(resources, "headphones").map_err(err)?, source);
+        }
+
+        Ok(exhibit)
+    }
+
+    fn bx(
+        &self,
+        desc: &BoxDesc,
+        resources: &GlobalResources,
+        panels: &TextPanels,
+        catalog: &Catalog,
+    ) -> Result<BoxBuilder, LayoutError> {
+        let err = |kind| LayoutError {
+            element: Some(desc.name.clone()),
+            line: line_of(&self.source, &desc.name),
+            kind,
+        };
+
+        let (width, height, depth) = desc.size;
+        let size = Vec3::new(width, height, depth);
+        let mut bx = match desc.glass {
+            true => BoxBuilder::glass_case(desc.position.clone(), size),
+            false => BoxBuilder::new(desc.position.clone(), size),
+        }
+        .with_name(desc.name.as_str());
+
+        if let Some(offset) = desc.offset {
+            bx = bx.with_offset(offset);
+        }
+        if let Some(texture) = &desc.texture {
+            bx = bx.with_texture(texture_handle(resources, texture).map_err(err)?);
+        }
+        for (&face, texture) in desc.face_textures.iter() {
+            bx = bx.with_face_texture(face.into(), texture_handle(resources, texture).map_err(err)?);
+        }
+        for (face, decal) in desc.decals.iter() {
+            if matches!(face, BoxFaceDesc::Top | BoxFaceDesc::Bottom) {
+                return Err(err(LayoutErrorKind::InvalidDecal));
+            }
+            bx = bx.with_decal((*face).into(), decal_of(decal, resources, panels, catalog).map_err(err)?);
+        }
+        if let Some(collision) = desc.collision {
+            bx = bx.with_collision(collision);
+        }
+        if let Some(radius) = desc.radius {
+            bx = bx.with_radius(radius);
+        }
+        if let Some(action) = &desc.action {
+            bx = bx.with_action(action_of(action, resources, panels, catalog).map_err(err)?);
+        }
+
+        Ok(bx)
+    }
+
+    fn artifact(
+        &self,
+        desc: &ArtifactDesc,
+        resources: &GlobalResources,
+        catalog: &Catalog,
+    ) -> Result<ArtifactBuilder, LayoutError> {
+        let err = |kind| LayoutError {
+            element: Some(desc.id.clone()),
+            line: line_of(&self.source, &desc.id),
+            kind,
+        };
+        let entry = catalog
+            .get(&desc.id)
+            .ok_or_else(|| err(LayoutErrorKind::UnknownExhibit(desc.id.clone())))?;
+
+        let model = entry.model.as_ref().ok_or_else(|| err(LayoutErrorKind::NoModel))?;
+        let model = resources.models
+            .get(model.as_str())
+            .cloned()
+            .ok_or_else(|| err(LayoutErrorKind::UnknownModel(model.clone())))?;
+        let mut artifact = ArtifactBuilder::new(desc.position.clone(), model.clone())
+            .with_name(desc.id.as_str())
+            .with_action(InteractableAction::Inspect {
+                name: entry.title.clone(),
+                model,
+                scale: desc.scale.unwrap_or(1.),
+            });
+
+        if let Some((width, height, depth)) = desc.pedestal {
+            artifact = artifact.with_pedestal_size(Vec3::new(width, height, depth));
+        }
+        if let Some(texture) = &desc.texture {
+            artifact = artifact.with_pedestal_texture(texture_handle(resources, texture).map_err(err)?);
+        }
+        if let Some(scale) = desc.scale {
+            artifact = artifact.with_scale(scale);
+        }
+        if let Some(rotation) = desc.rotation {
+            artifact = artifact.with_rotation(rotation.to_radians());
+        }
+        if let Some(radius) = desc.radius {
+            artifact = artifact.with_radius(radius);
+        }
+
+        Ok(artifact)
+    }
+}
+
+/// Resolve the names referenced by an action
+fn action_of(
+    action: &ActionDesc,
+    resources: &GlobalResources,
+    panels: &TextPanels,
+    catalog: &Catalog,
+) -> Result<InteractableAction, LayoutErrorKind> {
+    Ok(match action {
+        ActionDesc::Tombstone { text, name, view } => InteractableAction::Tombstone {
+            text: *panels.0
+                .get(text)
+                .ok_or_else(|| LayoutErrorKind::UnknownText(text.clone()))?,
+            name: name.clone(),
+            view: view.as_ref().map(ViewPose::from),
+        },
+        ActionDesc::Exhibit(id) => {
+            let unknown = || LayoutErrorKind::UnknownExhibit(id.clone());
+            InteractableAction::Tombstone {
+                text: *panels.0.get(id).ok_or_else(unknown)?,
+                name: catalog.get(id).ok_or_else(unknown)?.title.clone(),
+                view: None,
+            }
+        },
+        ActionDesc::Audio(source) => InteractableAction::Audio {
+            source: resources.audio
+                .get(source.as_str())
+                .cloned()
+                .ok_or_else(|| LayoutErrorKind::UnknownAudio(source.clone()))?,
+        },
+        ActionDesc::Image { texture, name } => InteractableAction::ViewImage {
+            image: match resources.images.get(texture.as_str()) {
+                Some(image) => image.clone(),
+                None => texture_handle(resources, texture)?,
+            },
+            name: name.clone(),
+        },
+        ActionDesc::Tooltip(tip) => InteractableAction::Tooltip(tip.clone()),
+    })
+}
+
+/// Create a decal, resolving the names it references
+fn decal_of(
+    desc: &DecalDesc,
+    resources: &GlobalResources,
+    panels: &TextPanels,
+    catalog: &Catalog,
+) -> Result<DecalBuilder, LayoutErrorKind> {
+    let mut decal = DecalBuilder::new(desc.start, desc.width, desc.height);
+
+    if let Some(side) = desc.side {
+        decal = decal.with_side(side.into());
+    }
+    if let Some(offset) = desc.offset {
+        decal = decal.with_offset(offset);
+    }
+    if let Some(texture) = &desc.texture {
+        decal = decal.with_texture(texture_handle(resources, texture)?);
+    }
+    decal = match desc.tiling {
+        Some(Tiling::Tiles(wide, tall)) => decal.with_tiles(wide, tall),
+        Some(_) => return Err(LayoutErrorKind::InvalidTiling),
+        None => decal,
+    };
+    if let Some(mirror) = desc.mirror {
+        decal = decal.with_mirror(mirror);
+    }
+    if let Some(transparent) = desc.transparent {
+        decal = decal.with_transparency(transparent);
+    }
+    if let Some(action) = &desc.action {
+        decal = decal.with_action(action_of(action, resources, panels, catalog)?);
+    }
+
+    Ok(decal)
+}
+
+/// Look up a texture by the name used in the layout file
+fn texture_handle(resources: &GlobalResources, name: &str) -> Result<Handle<Image>, LayoutErrorKind> {
+    resources.textures
+        .get(name)
+        .cloned()
+        .ok_or_else(|| LayoutErrorKind::UnknownTexture(name.to_owned()))
+}
+
+/// Find the line that the element with the given name or exhibit id is defined on, or 0 if it
+/// can't be found
+fn line_of(source: &str, name: &str) -> usize {
+    let fields = [format!("name: \"{}\"", name), format!("id: \"{}\"", name)];
+    source
+        .lines()
+        .position(|line| fields.iter().any(|field| line.trim_start().starts_with(field)))
+        .map_or(0, |idx| idx + 1)
+}
+
+/// Find the name of the element defined closest before the given line
+fn element_before(source: &str, line: usize) -> Option<String> {
+    source
+        .lines()
+        .take(line)
+        .filter_map(|line| {
+            let line = line.trim_start();
+            line.strip_prefix("name: \"").or_else(|| line.strip_prefix("id: \""))
+        })
+        .last()
+        .and_then(|rest| rest.split('"').next())
+        .map(str::to_owned)
+}
+
+/// Asset loader for `.layout.ron` files
+#[derive(Default)]
+pub struct LayoutLoader;
+
+impl AssetLoader for LayoutLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let layout = Layout::parse(std::str::from_utf8(bytes)?)?;
+            load_context.set_default_asset(LoadedAsset::new(layout));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["layout.ron"]
+    }
+}
+
+/// Marker component for entities spawned from the layout file, despawned when the file changes
+#[derive(Component)]
+pub struct LayoutGeometry;
+
+/// Build the museum's walls and floors once the layout and catalog files have finished loading,
+/// and rebuild them whenever the layout or the text panels change. The old walls and floors are
+/// kept if the modified layout has errors
+pub fn spawn_layout(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<Layout>>,
+    layouts: Res<Assets<Layout>>,
+    catalogs: Res<Assets<Catalog>>,
+    resources: Res<GlobalResources>,
+    panels: Res<TextPanels>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    geometry: Query<Entity, With<LayoutGeometry>>,
+) {
+    let mut modified = false;
+    for event in events.iter() {
+        if let AssetEvent::Created { handle } | AssetEvent::Modified { handle } = event {
+            modified |= *handle == resources.layout;
+        }
+    }
+    if !modified && !panels.is_changed() {
+        return
+    }
+
+    // Changes to either file will trigger another rebuild once both have loaded
+    if let (Some(layout), Some(catalog)) = (layouts.get(&resources.layout), catalogs.get(&resources.catalog)) {
+        let built = layout
+            .scene(&resources, &panels, catalog)
+            .and_then(|scene| scene
+                .finish(&mut commands, &mut meshes, &mut materials)
+                .map_err(|e| layout.plan_error(e))
+            );
+
+        match built {
+            Ok(spawned) => {
+                geometry.for_each(|entity| commands.entity(entity).despawn_recursive());
+                for entity in spawned {
+                    commands.entity(entity).insert(LayoutGeometry);
+                }
+                info!("Built museum layout");
+            },
+            Err(e) => error!("Failed to build museum layout: {}", e),
+        }
+    }
+}
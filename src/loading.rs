@@ -0,0 +1,264 @@
+use bevy::{
+    asset::{AssetPlugin, LoadState},
+    audio::AudioLoader,
+    gltf::GltfPlugin,
+    render::{mesh::skinning::SkinnedMeshInverseBindposes, texture::ImagePlugin},
+    scene::ScenePlugin,
+};
+
+use super::*;
+
+/// Marker component for the loading screen, despawned once everything has loaded
+#[derive(Component)]
+pub struct LoadingScreen;
+
+/// Marker component for the filled part of the loading screen's progress bar
+#[derive(Component)]
+pub struct ProgressBar;
+
+/// Marker component for the text listing assets that failed to load
+#[derive(Component)]
+pub struct AssetWarnings;
+
+/// Paths of every asset in [GlobalResources] that failed to load
+#[derive(Default)]
+pub struct FailedAssets(pub Vec<String>);
+
+/// Cover the window with the museum's title and a progress bar while its assets load
+pub fn spawn_loading_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                position_type: PositionType::Absolute,
+                // Columns go from the bottom up unless reversed
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..default()
+        })
+        .insert(LoadingScreen)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                style: Style {
+                    margin: Rect {
+                        bottom: Val::Px(32.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                text: Text::with_section(
+                    TITLE,
+                    TextStyle {
+                        font: asset_server.load("fonts/times-new-roman.ttf"),
+                        font_size: 64.,
+                        color: Color::WHITE,
+                    },
+                    default(),
+                ),
+                ..default()
+            });
+
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(40.), Val::Px(12.)),
+                        ..default()
+                    },
+                    color: Color::rgb(0.3, 0.3, 0.3).into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(0.), Val::Percent(100.)),
+                                ..default()
+                            },
+                            color: Color::WHITE.into(),
+                            ..default()
+                        })
+                        .insert(ProgressBar);
+                });
+        });
+}
+
+/// Fill the progress bar with the share of [GlobalResources] that has finished loading, and enter
+/// the museum once every asset has either loaded or failed to
+pub fn update_progress(
+    asset_server: Res<AssetServer>,
+    resources: Res<GlobalResources>,
+    mut state: ResMut<State<AppState>>,
+    mut bars: Query<&mut Style, With<ProgressBar>>,
+) {
+    let handles = resources.handles();
+    let finished = finished(&asset_server, &handles);
+
+    for mut bar in bars.iter_mut() {
+        bar.size.width = Val::Percent(100. * finished as f32 / handles.len() as f32);
+    }
+    if finished == handles.len() {
+        // Only fails if the state is already changing
+        let _ = state.set(AppState::Museum);
+    }
+}
+
+/// Remove the loading screen once everything has loaded
+pub fn despawn_loading_screen(
+    mut commands: Commands,
+    screens: Query<Entity, With<LoadingScreen>>,
+) {
+    for screen in screens.iter() {
+        commands.entity(screen).despawn_recursive();
+    }
+}
+
+/// Count the assets that have either loaded or failed to load
+fn finished(asset_server: &AssetServer, handles: &[HandleId]) -> usize {
+    handles
+        .iter()
+        .filter(|handle| matches!(asset_server.get_load_state(**handle), LoadState::Loaded | LoadState::Failed))
+        .count()
+}
+
+/// Get the path of every asset that failed to load
+fn failures(asset_server: &AssetServer, handles: &[HandleId]) -> Vec<String> {
+    handles
+        .iter()
+        .filter(|handle| asset_server.get_load_state(**handle) == LoadState::Failed)
+        .map(|handle| asset_server
+            .get_handle_path(*handle)
+            .map_or_else(|| format!("{:?}", handle), |path| path.path().display().to_string())
+        )
+        .collect()
+}
+
+/// Spawn the initially empty list of assets that failed to load, kept above everything else
+pub fn spawn_asset_warnings(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    bottom: Val::Percent(2.),
+                    left: Val::Percent(2.),
+                    ..default()
+                },
+                ..default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/times-new-roman.ttf"),
+                    font_size: 18.,
+                    color: Color::rgb(1., 0.8, 0.),
+                },
+                default(),
+            ),
+            transform: Transform::from_xyz(0., 0., 100.),
+            ..default()
+        })
+        .insert(AssetWarnings);
+}
+
+/// Log assets as they fail to load, and list them on screen
+pub fn report_failures(
+    asset_server: Res<AssetServer>,
+    resources: Res<GlobalResources>,
+    mut failed: ResMut<FailedAssets>,
+    mut warnings: Query<&mut Text, With<AssetWarnings>>,
+) {
+    for path in failures(&asset_server, &resources.handles()) {
+        if !failed.0.contains(&path) {
+            error!("Failed to load asset '{}'", path);
+            failed.0.push(path);
+        }
+    }
+
+    if failed.is_changed() && !failed.0.is_empty() {
+        for mut text in warnings.iter_mut() {
+            text.sections[0].value = format!("Failed to load:\n{}", failed.0.join("\n"));
+        }
+    }
+}
+
+/// Load every asset in [GlobalResources] without opening a window, printing the ones that failed
+/// to load, then check that the layout can be built from them and the catalog. Returns the process
+/// exit code, which is 1 if any asset failed or the layout can't be built
+pub fn check_assets() -> i32 {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin)
+        .add_plugin(ImagePlugin)
+        .add_asset::<AudioSource>()
+        .init_asset_loader::<AudioLoader>()
+        .add_asset::<Mesh>()
+        .add_asset::<StandardMaterial>()
+        .add_asset::<SkinnedMeshInverseBindposes>()
+        .add_plugin(ScenePlugin)
+        .add_plugin(GltfPlugin)
+        .add_asset::<Layout>()
+        .init_asset_loader::<LayoutLoader>()
+        .add_asset::<Catalog>()
+        .init_asset_loader::<CatalogLoader>()
+        .init_resource::<GlobalResources>()
+        .add_startup_system(load_resources);
+
+    loop {
+        app.update();
+        let asset_server = app.world.resource::<AssetServer>();
+        let handles = app.world.resource::<GlobalResources>().handles();
+        if finished(asset_server, &handles) < handles.len() {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            continue
+        }
+
+        let failed = failures(asset_server, &handles);
+        for path in failed.iter() {
+            eprintln!("Failed to load asset '{}'", path);
+        }
+        println!("{} of {} assets loaded", handles.len() - failed.len(), handles.len());
+        return match failed.is_empty() && check_layout(&app.world) {
+            true => 0,
+            false => 1,
+        }
+    }
+}
+
+/// Resolve the loaded layout against [GlobalResources] and the catalog, printing why it can't be
+/// built. Returns whether it can, which is false if either file failed to load
+fn check_layout(world: &World) -> bool {
+    let resources = world.resource::<GlobalResources>();
+    let layout = world.resource::<Assets<Layout>>().get(&resources.layout);
+    let catalog = world.resource::<Assets<Catalog>>().get(&resources.catalog);
+    let (layout, catalog) = match (layout, catalog) {
+        (Some(layout), Some(catalog)) => (layout, catalog),
+        _ => return false,
+    };
+
+    // Text panels are only spawned with a window, so any entity stands in for each of them
+    let panels = TextPanels(setup::PANELS
+        .into_iter()
+        .chain(catalog.ids())
+        .map(|name| (name.to_owned(), Entity::from_raw(0)))
+        .collect());
+    match layout.check(resources, &panels, catalog) {
+        Ok(()) => {
+            println!("Museum layout is valid");
+            true
+        },
+        Err(e) => {
+            eprintln!("Failed to build museum layout: {}", e);
+            false
+        },
+    }
+}
@@ -1,83 +1,284 @@
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
 pub mod setup;
 pub mod scene;
+pub mod layout;
+pub mod plan;
+pub mod catalog;
+pub mod loading;
+pub mod viewer;
+pub mod collision;
+pub mod grid;
+pub mod settings;
+pub mod menu;
 
 use bevy::{
-    ecs::system::EntityCommands,
+    asset::{AssetServerSettings, HandleId},
+    core::{FixedTimestep, FixedTimesteps},
     input::{
-        mouse::{MouseButtonInput, MouseMotion},
+        mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
         ElementState, keyboard::KeyboardInput,
     },
     prelude::*,
     render::{
         mesh::{Indices, PrimitiveTopology},
-        render_resource::{AddressMode, FilterMode, Face},
-        texture::{CompressedImageFormats, ImageType},
+        render_resource::{
+            AddressMode, Extent3d, FilterMode, Face, SamplerDescriptor, TextureDimension, TextureFormat,
+        },
     }, audio::AudioSink,
+    utils::HashMap,
 };
 use smooth_bevy_cameras::{
     LookAngles, LookTransform, LookTransformBundle, LookTransformPlugin, Smoother,
 };
+use layout::{Layout, LayoutLoader, TextPanels};
+use catalog::{Catalog, CatalogLoader};
+use collision::LineCollider;
+use grid::SpatialGrid;
+use settings::{key_name, Settings};
 
 fn main() {
+    if std::env::args().any(|arg| arg == "--check-assets") {
+        std::process::exit(loading::check_assets());
+    }
+    let (settings, settings_source) = settings::load();
+
     App::new()
         .insert_resource(WindowDescriptor {
-            title: "Museum".to_owned(),
+            title: TITLE.to_owned(),
             present_mode: bevy::window::PresentMode::Fifo,
             ..Default::default()
         })
+        // Pick up changes to the layout file while running
+        .insert_resource(AssetServerSettings {
+            watch_for_changes: cfg!(not(target_arch = "wasm32")),
+            ..default()
+        })
         .init_resource::<GlobalResources>()
+        .init_resource::<TextPanels>()
+        .init_resource::<loading::FailedAssets>()
+        .init_resource::<SpatialGrid>()
+        .init_resource::<menu::MenuPage>()
+        .insert_resource(settings)
+        .insert_resource(settings_source)
         //.insert_resource(Msaa { samples: 4 })
         .add_plugins(DefaultPlugins)
         .add_plugin(LookTransformPlugin)
+        .add_asset::<Layout>()
+        .init_asset_loader::<LayoutLoader>()
+        .add_asset::<Catalog>()
+        .init_asset_loader::<CatalogLoader>()
+        .add_state(AppState::Loading)
         .add_startup_system(load_resources.before(setup::setup))
         .add_startup_system(setup::setup)
-        .add_system(input)
-        .add_system(grab.before(bevy::input::keyboard::keyboard_input_system))
+        .add_startup_system(loading::spawn_asset_warnings)
+        .add_system_set(SystemSet::on_enter(AppState::Loading).with_system(loading::spawn_loading_screen))
+        .add_system_set(SystemSet::on_update(AppState::Loading).with_system(loading::update_progress))
+        .add_system_set(SystemSet::on_exit(AppState::Loading).with_system(loading::despawn_loading_screen))
+        .add_system_set(SystemSet::on_enter(AppState::Museum).with_system(lock_cursor))
+        .add_system_set(SystemSet::on_pause(AppState::Museum).with_system(menu::release_cursor))
+        .add_system_set(SystemSet::on_resume(AppState::Museum).with_system(lock_cursor))
+        .add_system_set(SystemSet::on_enter(AppState::Paused).with_system(menu::open_menu))
+        .add_system_set(SystemSet::on_update(AppState::Paused).with_system(menu::menu))
+        .add_system_set(SystemSet::on_exit(AppState::Paused).with_system(menu::close_menu))
+        .add_system_set(
+            SystemSet::new()
+                .with_run_criteria(FixedTimestep::step(MOVEMENT_STEP).with_label(MOVEMENT_TIMESTEP))
+                .with_system(movement.before(input))
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::Museum)
+                .with_system(input)
+                .with_system(fade_backdrop.after(input))
+                .with_system(viewer::view_image.after(input))
+                .with_system(menu::pause.before(viewer::view_image))
+                .with_system(grab.before(bevy::input::keyboard::keyboard_input_system))
+        )
         .add_system(setup::set_text_sizes)
+        .add_system(catalog::spawn_catalog.before(layout::spawn_layout))
+        .add_system(layout::spawn_layout)
+        .add_system(configure_textures)
+        .add_system(loading::report_failures)
+        .add_system_to_stage(CoreStage::PostUpdate, grid::update_grid)
+        .add_system_to_stage(CoreStage::PreUpdate, settings::reload_settings)
+        .add_system(settings::apply_settings)
+        .add_system_to_stage(CoreStage::PostUpdate, scene::apply_textures)
         .run();
 }
 
+/// Lock and hide the cursor once the museum has loaded or is resumed
+fn lock_cursor(mut windows: ResMut<Windows>) {
+    if let Some(win) = windows.get_primary_mut() {
+        win.set_cursor_lock_mode(true);
+        win.set_cursor_visibility(false);
+    }
+}
+
+/// Lock and hide the cursor again when a key is pressed or the window is clicked
 fn grab(
     keys: EventReader<KeyboardInput>,
+    mut mb: EventReader<MouseButtonInput>,
     mut windows: ResMut<Windows>
 ) {
-    if !keys.is_empty() {
-        windows.get_primary_mut().map(|win| {
+    let clicked = mb
+        .iter()
+        .any(|event| event.button == MouseButton::Left && event.state == ElementState::Released);
+    if !keys.is_empty() || clicked {
+        if let Some(win) = windows.get_primary_mut() {
             win.set_cursor_lock_mode(true);
             win.set_cursor_visibility(false);
-        });
+        }
+    }
+}
+
+/// Seconds between each step of the player's movement
+const MOVEMENT_STEP: f64 = 1. / 60.;
+/// Label of the fixed timestep that the player moves on
+const MOVEMENT_TIMESTEP: &str = "movement";
+/// Radians the camera turns per pixel that the mouse moves, before [Settings::sensitivity]
+const MOUSE_SENSITIVITY: f32 = 0.0017;
+/// Height of the head bob above and below [PLAYER_HEIGHT]
+const BOB_AMOUNT: f32 = 0.05;
+/// Speed of the head bob in metres per second
+const BOB_SPEED: f32 = 0.2;
+
+/// System running on a fixed timestep that moves the player with the keyboard, sliding along the
+/// walls they walk into, and bobs their head while they walk
+fn movement(
+    state: Res<State<AppState>>,
+    kb: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    grid: Res<SpatialGrid>,
+    mut players: Query<(&LookTransform, &mut Player)>,
+) {
+    if *state.current() != AppState::Museum {
+        return
+    }
+
+    let step = MOVEMENT_STEP as f32;
+    for (camera, mut player) in players.iter_mut() {
+        player.previous = player.position;
+        if player.viewed_text.is_some() || player.inspected.is_some() || player.viewed_image.is_some() {
+            continue
+        }
+        let dir = match camera.look_direction() {
+            Some(dir) => dir,
+            None => continue,
+        };
+
+        let keys = &settings.keys;
+        let mut movement = Vec2::default();
+        if kb.pressed(keys.forward) {
+            movement.y += 1.;
+        }
+        if kb.pressed(keys.back) {
+            movement.y -= 1.;
+        }
+        if kb.pressed(keys.right) {
+            movement.x -= 1.;
+        }
+        if kb.pressed(keys.left) {
+            movement.x += 1.;
+        }
+
+        if movement != Vec2::default() && settings.head_bob {
+            match player.up {
+                true => match player.cam_height >= BOB_AMOUNT {
+                    true => player.up = false,
+                    false => player.cam_height += BOB_SPEED * step,
+                },
+                false => match player.cam_height <= -BOB_AMOUNT {
+                    true => player.up = true,
+                    false => player.cam_height -= BOB_SPEED * step,
+                }
+            }
+        } else if player.cam_height.abs() >= 0.01 {
+            player.cam_height -= BOB_SPEED * player.cam_height.signum() * step;
+        }
+
+        if movement != Vec2::default() {
+            let yaw = Quat::from_axis_angle(Vec3::Y, LookAngles::from_vector(dir).get_yaw());
+            let movement = yaw * Vec3::new(movement.x, 0., movement.y).normalize() * settings.walk_speed * step;
+            let movement = Vec2::new(movement.x, movement.z);
+            let start = player.position;
+            let reach = Vec2::splat(PLAYER_RADIUS);
+            let walls = grid.colliders_in(start.min(start + movement) - reach, start.max(start + movement) + reach);
+            player.position = collision::slide(start, movement, PLAYER_RADIUS, &walls);
+        }
     }
 }
 
-/// System running every update used to update the player position and camera angles, as well as 
-/// check if the player is aiming at something that is interactable
+/// System running every update used to update the camera angles and draw the player between
+/// movement steps, as well as check if the player is aiming at something that is interactable
 fn input(
+    mut commands: Commands,
     mut mouse: EventReader<MouseMotion>,
-    mut mb: EventReader<MouseButtonInput>,
+    mut wheel: EventReader<MouseWheel>,
     kb: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
     mut players: Query<(&mut LookTransform, &mut Player, &mut Smoother)>,
-    mut windows: ResMut<Windows>,
-    objects: Query<&LineCollider>,
+    grid: Res<SpatialGrid>,
     tombstones: Query<&Interactable, (Without<InteractText>, Without<ExitPrompt>)>,
-    mut texts: Query<&mut Visibility, (Without<InteractText>, Without<ExitPrompt>)>,
+    mut backdrops: Query<&mut Backdrop>,
     mut interact_text: Query<(&mut Visibility, &mut Text), (With<InteractText>, Without<ExitPrompt>)>,
-    time: Res<Time>,
+    timesteps: Res<FixedTimesteps>,
     sinks: Res<Assets<AudioSink>>,
     audio: Res<Audio>,
     mut exit_prompt: Query<&mut Visibility, With<ExitPrompt>>,
+    mut inspected: Query<&mut Transform, With<Inspected>>,
 ) {
-    let sensitivity = 0.1 * time.delta_seconds();
     for (mut camera, mut player, mut smoother) in players.iter_mut() {
-        if let Some(txt) = player.viewed_text {
-            if kb.just_released(KeyCode::E) {
+        // The image viewer handles its own input
+        if player.viewed_image.is_some() {
+            break
+        }
+
+        if let Some(model) = player.inspected {
+            // Read every frame, even before the model has spawned, so that old movement isn't
+            // applied all at once later
+            let turn = mouse.iter().fold(Vec2::ZERO, |turn, event| turn + event.delta);
+            let zoom = wheel.iter().fold(0., |zoom, event| zoom + match event.unit {
+                MouseScrollUnit::Line => event.y * 0.25,
+                MouseScrollUnit::Pixel => event.y * 0.01,
+            });
+
+            if kb.just_released(settings.keys.interact) {
+                exit_prompt
+                    .iter_mut()
+                    .for_each(|mut prompt| prompt.is_visible = false);
+                commands.entity(model).despawn_recursive();
+                player.inspected = None;
+                camera.eye = player.old_eye;
+                camera.target = player.old_target;
+                *smoother = settings.smoother();
+            } else {
+                exit_prompt
+                    .iter_mut()
+                    .for_each(|mut prompt| prompt.is_visible = true);
+
+                if let Ok(mut transform) = inspected.get_mut(model) {
+                    transform.rotation = Quat::from_rotation_y(turn.x * INSPECT_ROTATE_SPEED)
+                        * Quat::from_rotation_x(turn.y * INSPECT_ROTATE_SPEED)
+                        * transform.rotation;
+                }
+                player.inspect_distance = (player.inspect_distance - zoom)
+                    .clamp(INSPECT_MIN_DISTANCE, INSPECT_MAX_DISTANCE);
+                camera.eye = INSPECT_POINT + Vec3::Z * player.inspect_distance;
+                camera.target = INSPECT_POINT;
+            }
+            break
+        }
+
+        if player.viewed_text.is_some() {
+            if kb.just_released(settings.keys.interact) {
                 exit_prompt
                     .iter_mut()
                     .for_each(|mut prompt| prompt.is_visible = false);
-                texts.get_mut(txt).unwrap().is_visible = false;
+                backdrops.iter_mut().for_each(|mut backdrop| backdrop.hide());
                 player.viewed_text = None;
                 camera.eye = player.old_eye;
                 camera.target = player.old_target;
-                *smoother = Smoother::new(0.7);
+                *smoother = settings.smoother();
             } else {
                 exit_prompt
                     .iter_mut()
@@ -88,13 +289,10 @@ fn input(
 
         if let Some(dir) = camera.look_direction() {
             let mut angles = LookAngles::from_vector(dir);
-            let yaw_rot = Quat::from_axis_angle(Vec3::Y, angles.get_yaw());
-            let rot_x = yaw_rot * Vec3::X;
-            let rot_y = yaw_rot * Vec3::Y;
-            let rot_z = yaw_rot * Vec3::Z;
-
+            let sensitivity = MOUSE_SENSITIVITY * settings.sensitivity;
+            let invert = if settings.invert_y { -1. } else { 1. };
             for event in mouse.iter() {
-                angles.add_pitch(-event.delta.y * sensitivity);
+                angles.add_pitch(-event.delta.y * sensitivity * invert);
                 angles.add_yaw(-event.delta.x * sensitivity);
             }
 
@@ -105,137 +303,163 @@ fn input(
                 angles.set_yaw(0.01);
             }
 
-            let movespeed = 0.17;//5.5 * 0.2;//time.delta_seconds();
-            let mut movement = Vec2::default();
-            
-            if kb.pressed(KeyCode::W) {
-                movement.y += movespeed;
-            } 
-            if kb.pressed(KeyCode::S) {
-                movement.y -= movespeed;
-            }
-
-            if kb.pressed(KeyCode::D) {
-                movement.x -= movespeed;
-            }
-            if kb.pressed(KeyCode::A) {
-                movement.x += movespeed;
-            }
-            let speed = movement.length();
-            let movement_3d = Vec3::new(movement.x, 0., movement.y);
-
-            let mut pos = camera.eye + (movement_3d.x * rot_x + movement_3d.y * rot_y + movement_3d.z * rot_z);
-            let mut pos2d = Vec2::new(pos.x, pos.z);
-            const BOB_AMOUNT: f32 = 0.05;
-            const BOB_SPEED: f32 = 0.2;
-
-            if movement != Vec2::default() {
-                match player.up {
-                    true => match player.cam_height >= BOB_AMOUNT {
-                        true => player.up = false,
-                        false => player.cam_height += BOB_SPEED * time.delta_seconds(),
-                    },
-                    false => match player.cam_height <= -BOB_AMOUNT {
-                        true => player.up = true,
-                        false => player.cam_height -= BOB_SPEED * time.delta_seconds(),
-                    }
-                }
-                
-                for object in objects.iter() {
-                    let dot = ( ( (pos2d.x - object.from.x) * (object.to.x - object.from.x)) + ((pos2d.y - object.from.y) * (object.to.y - object.from.y))) / (object.len.powi(2));
-                    let closest = Vec2::new(
-                        object.from.x + (dot * (object.to.x - object.from.x)),
-                        object.from.y + (dot * (object.to.y - object.from.y))
-                    );
-
-                    let d_from = closest.distance(object.from);
-                    let d_to = closest.distance(object.to);
-
-                    const ERROR: f32 = 0.5;
-                    if d_from + d_to >= object.len - ERROR && d_from + d_to <= object.len + ERROR {
-                        let collision_distance = closest.distance(pos2d);
-                        if collision_distance <= PLAYER_RADIUS {
-                            let unit = (closest - pos2d).normalize();
-                            pos2d -= unit * speed;
-                        }
-                    }
-                }
-            } else {
-                if player.cam_height.abs() >= 0.01 {
-                    player.cam_height -= BOB_SPEED * player.cam_height.signum() * time.delta_seconds();
-                }
-            }
-
             let (mut interact_visibility, mut interact_text) = interact_text.get_single_mut().unwrap();
             interact_visibility.is_visible = false;
 
-            pos.x = pos2d.x;
-            pos.y = PLAYER_HEIGHT + player.cam_height;
-            pos.z = pos2d.y;
-            
+            // Show the player between their last two movement steps, as far as this frame is
+            // through the current step
+            let overstep = timesteps
+                .get(MOVEMENT_TIMESTEP)
+                .map_or(1., |timestep| timestep.overstep_percentage() as f32)
+                .min(1.);
+            let pos2d = player.previous.lerp(player.position, overstep);
+
             angles.assert_not_looking_up();
-            camera.eye = pos;
+            camera.eye = Vec3::new(pos2d.x, PLAYER_HEIGHT + player.cam_height, pos2d.y);
             camera.target = camera.eye + camera.radius() * angles.unit_vector();
             player.old_eye = camera.eye;
             player.old_target = camera.target;
 
-            for interactable in tombstones.iter() {
-                if interactable.point.distance(pos2d) < interactable.radius {
-                    if kb.just_released(KeyCode::E) {
-                        match &interactable.action {
-                            InteractableAction::Tombstone { text, .. } => {
-                                texts.get_mut(*text).unwrap().is_visible = true; 
-                                player.viewed_text = Some(*text);
-                                camera.eye = Vec3::new(4., 1., 5.5);
-                                camera.target = Vec3::new(4., 1., 6.);
-                                *smoother = Smoother::new(0.);
-                            },
-                            InteractableAction::Audio { source } => {
-                                if let Some((sink, playing_source)) = &player.playing_audio {
-                                    if playing_source == source {
-                                        sinks.get(sink).map(|sink| match sink.is_paused() {
+            // The nearest interactable in range is used, so that actions can be placed close together
+            let nearest = grid
+                .interactables_at(pos2d)
+                .iter()
+                .filter_map(|&entity| tombstones.get(entity).ok())
+                .filter(|interactable| interactable.point.distance(pos2d) < interactable.radius)
+                .min_by(|a, b| a.point.distance(pos2d).total_cmp(&b.point.distance(pos2d)));
+            if let Some(interactable) = nearest {
+                if kb.just_released(settings.keys.interact) {
+                    match &interactable.action {
+                        InteractableAction::Tombstone { text, view, .. } => {
+                            backdrops
+                                .iter_mut()
+                                .for_each(|mut backdrop| backdrop.show(*text, view.is_none()));
+                            player.viewed_text = Some(*text);
+                            if let Some(view) = view {
+                                camera.eye = view.eye;
+                                camera.target = view.target;
+                            }
+                        },
+                        InteractableAction::ViewImage { image, .. } => {
+                            player.viewed_image = Some(image.clone());
+                        },
+                        InteractableAction::Inspect { model, scale, .. } => {
+                            let transform = Transform::from_translation(INSPECT_POINT)
+                                .with_scale(Vec3::splat(*scale));
+                            let model = model.clone();
+                            player.inspected = Some(commands
+                                .spawn_bundle(TransformBundle::from_transform(transform))
+                                .insert(Inspected)
+                                .with_children(|parent| {
+                                    parent.spawn_scene(model);
+                                })
+                                .id()
+                            );
+                            player.inspect_distance = INSPECT_DISTANCE;
+                            camera.eye = INSPECT_POINT + Vec3::Z * INSPECT_DISTANCE;
+                            camera.target = INSPECT_POINT;
+                            *smoother = Smoother::new(0.);
+                        },
+                        InteractableAction::Audio { source } => {
+                            if let Some((sink, playing_source)) = &player.playing_audio {
+                                if playing_source == source {
+                                    if let Some(sink) = sinks.get(sink) {
+                                        match sink.is_paused() {
                                             true => sink.play(),
                                             false => sink.pause(),
-                                        });
-                                        break;
+                                        }
                                     }
-                                    sinks.get(sink).map(AudioSink::stop);
+                                    break;
                                 }
-                                let sink = audio.play(source.clone());        
-                                let sink = sinks.get_handle(sink);
-                                player.playing_audio = Some((sink, source.clone()));
-                            },
-                            _ => (),
-                        }
-                    } else {
-                        interact_visibility.is_visible = true;
-                        interact_text.sections[0].value = match &interactable.action {
-                            InteractableAction::Tombstone { name, .. } => format!("[e] Read {}", name),
-                            InteractableAction::Audio { source } => match player.playing_audio
-                                .as_ref()
-                                .map_or(false, |(sink, src)| src.id == source.id && !sinks.get(sink).unwrap().is_paused()) {
-                                false => "[e] Play Audio",
-                                true => "[e] Pause Audio",
-                            }.to_owned(),
-                            InteractableAction::Tooltip(tip) => tip.to_string(),
-                        };
+                                sinks.get(sink).map(AudioSink::stop);
+                            }
+                            let sink = audio.play(source.clone());        
+                            let sink = sinks.get_handle(sink);
+                            player.playing_audio = Some((sink, source.clone()));
+                        },
+                        _ => (),
                     }
-                    break;
+                } else {
+                    interact_visibility.is_visible = true;
+                    let key = key_name(settings.keys.interact);
+                    interact_text.sections[0].value = match &interactable.action {
+                        InteractableAction::Tombstone { name, .. } => format!("[{}] Read {}", key, name),
+                        InteractableAction::Inspect { name, .. } => format!("[{}] Inspect {}", key, name),
+                        InteractableAction::ViewImage { name, .. } => format!("[{}] View {}", key, name),
+                        InteractableAction::Audio { source } => match player.playing_audio
+                            .as_ref()
+                            .is_some_and(|(sink, src)| src.id == source.id && !sinks.get(sink).unwrap().is_paused()) {
+                            false => format!("[{}] Play Audio", key),
+                            true => format!("[{}] Pause Audio", key),
+                        },
+                        InteractableAction::Tooltip(tip) => tip.clone(),
+                    };
                 }
             }
         }
     }
 
-    for event in mb.iter() {
-        if event.button == MouseButton::Left && event.state == ElementState::Released {
-            windows.get_primary_mut().map(|window| {
-                window.set_cursor_lock_mode(true);
-                window.set_cursor_visibility(false);
-            });
+}
+
+/// Seconds taken to fade the reading backdrop and text in or out
+const BACKDROP_FADE_TIME: f32 = 0.25;
+
+/// Fade the reading backdrop and the text panel shown over it towards being shown or hidden
+fn fade_backdrop(
+    time: Res<Time>,
+    mut backdrops: Query<(&mut Backdrop, &mut UiColor, &mut Visibility)>,
+    mut texts: Query<(&mut Text, &mut Visibility), Without<Backdrop>>,
+) {
+    for (mut backdrop, mut color, mut visibility) in backdrops.iter_mut() {
+        if backdrop.text.is_some() && backdrop.visible != backdrop.text {
+            if let Some((_, mut visible)) = backdrop.visible.and_then(|text| texts.get_mut(text).ok()) {
+                visible.is_visible = false;
+            }
+            backdrop.visible = backdrop.text;
+        }
+
+        let step = time.delta_seconds() / BACKDROP_FADE_TIME;
+        let fade = match backdrop.text {
+            Some(_) => (backdrop.fade + step).min(1.),
+            None => (backdrop.fade - step).max(0.),
+        };
+        let shown = match backdrop.visible {
+            Some(shown) => shown,
+            None => continue,
+        };
+        if fade == backdrop.fade && visibility.is_visible == (backdrop.opaque && fade > 0.) {
+            continue
+        }
+
+        backdrop.fade = fade;
+        color.0.set_a(fade);
+        visibility.is_visible = backdrop.opaque && fade > 0.;
+        if let Ok((mut text, mut visible)) = texts.get_mut(shown) {
+            visible.is_visible = fade > 0.;
+            text.sections
+                .iter_mut()
+                .for_each(|section| { section.style.color.set_a(fade); });
+        }
+        if fade == 0. {
+            backdrop.visible = None;
         }
     }
 }
 
+/// Name of the museum, shown in the window title and on the loading screen
+const TITLE: &str = "Museum";
+
+/// Whether the museum is still loading, can be walked around or is paused
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum AppState {
+    /// Assets in [GlobalResources] are loading behind the loading screen
+    Loading,
+    /// Everything has loaded and the player can move
+    Museum,
+    /// The pause menu is open over the museum, which is kept as it was until it is resumed
+    Paused,
+}
+
 /// Various global state items 
 pub struct GlobalState {
     pub interact_text: Entity,
@@ -244,18 +468,40 @@ pub struct GlobalState {
 const PLAYER_RADIUS: f32 = 0.5;
 const PLAYER_HEIGHT: f32 = 1.6;
 
+/// Point far below the museum where inspected models are shown, so nothing else is in view
+const INSPECT_POINT: Vec3 = bevy::math::const_vec3!([0., -100., 0.]);
+/// Initial distance of the camera from an inspected model
+const INSPECT_DISTANCE: f32 = 2.;
+/// Closest the camera can zoom to an inspected model
+const INSPECT_MIN_DISTANCE: f32 = 0.5;
+/// Furthest the camera can zoom from an inspected model
+const INSPECT_MAX_DISTANCE: f32 = 6.;
+/// Radians an inspected model turns per pixel of mouse movement
+const INSPECT_ROTATE_SPEED: f32 = 0.01;
+
 /// Marker component specifying that a collision object is controlled with the keyboard and mouse
 #[derive(Component, Default)]
 struct Player {
     /// Viewed text entity
     viewed_text: Option<Entity>,
+    /// Image open in the image viewer
+    viewed_image: Option<Handle<Image>>,
+    /// Copy of the model being inspected
+    inspected: Option<Entity>,
+    /// Distance of the camera from the inspected model
+    inspect_distance: f32,
     /// The currently playing audio track
     playing_audio: Option<(Handle<AudioSink>, Handle<AudioSource>)>,
     
-    /// Used to restore state after exiting the read dialogue
+    /// Used to restore state after exiting the read dialogue or inspect mode
     old_eye: Vec3,
-    /// Used to restore state after exiting the read dialogue
+    /// Used to restore state after exiting the read dialogue or inspect mode
     old_target: Vec3,
+    /// Position on the floor plan after the last movement step
+    position: Vec2,
+    /// Position on the floor plan before the last movement step, used to draw the player between
+    /// steps
+    previous: Vec2,
     /// Camera offset used to add head bobbing
     cam_height: f32,
     /// If head bob is travelling up or down
@@ -263,28 +509,39 @@ struct Player {
 }
 
 
-/// Collider component that specifies a wall has collision
-#[derive(Component)]
-pub struct LineCollider {
-    /// Start of the line segment
-    from: Vec2,
-    /// End of the line segment
-    to: Vec2,
-    /// Cached length of the collider
-    len: f32,
+/// Where the camera looks from while reading a tombstone
+#[derive(Clone, Copy, Debug)]
+pub struct ViewPose {
+    pub eye: Vec3,
+    pub target: Vec3,
 }
 
 /// Action that can be taken when interacting with something
 #[derive(Clone)]
 pub enum InteractableAction {
+    /// Show a text panel, with the camera moved to the view pose if there is one, or over a
+    /// full-screen backdrop otherwise
     Tombstone {
         text: Entity,
-        name: &'static str,
+        name: String,
+        view: Option<ViewPose>,
     },
     Audio {
         source: Handle<AudioSource>,
     },
-    Tooltip(&'static str),
+    /// Show an image full-screen, where it can be panned with the mouse and zoomed with the wheel
+    ViewImage {
+        name: String,
+        image: Handle<Image>,
+    },
+    /// Show a copy of a model on its own, which can be turned with the mouse and zoomed with the
+    /// wheel
+    Inspect {
+        name: String,
+        model: Handle<Scene>,
+        scale: f32,
+    },
+    Tooltip(String),
 }
 
 /// Any interactable object
@@ -298,115 +555,221 @@ pub struct Interactable {
 #[derive(Component)]
 pub struct InteractText;
 
+/// Full-screen panel faded in behind a text panel while it is read
+#[derive(Component, Default)]
+pub struct Backdrop {
+    /// Text panel being read
+    text: Option<Entity>,
+    /// Text panel that is visible, which stays set while it fades out
+    visible: Option<Entity>,
+    /// Whether the panel covers the screen behind the text, or only the text is shown
+    opaque: bool,
+    /// How far the transition has gone, from 0 when hidden to 1 when fully shown
+    fade: f32,
+}
+
+impl Backdrop {
+    /// Start fading in a text panel, over the backdrop if it is opaque
+    pub fn show(&mut self, text: Entity, opaque: bool) {
+        self.text = Some(text);
+        self.opaque = opaque;
+    }
+
+    /// Start fading out the text panel and backdrop
+    pub fn hide(&mut self) {
+        self.text = None;
+    }
+}
+
+/// Marker component for the copy of a model shown in inspect mode
+#[derive(Component)]
+pub struct Inspected;
+
 #[derive(Component)]
 pub struct ExitPrompt;
 
+/// Handles to every texture, audio track and model in the museum, keyed by their file names
+/// without extensions so that the layout and catalog files can refer to them
 #[derive(Default)]
 pub struct GlobalResources {
-    birch_floor: Handle<Image>,
-    oak_floor: Handle<Image>,
-    flagstone_floor: Handle<Image>,
-    tile_floor: Handle<Image>,
-    red_tile_floor: Handle<Image>,
-    linoleum_floor: Handle<Image>,
-    blue_trimmed_wall: Handle<Image>,
-    red_trimmed_wall: Handle<Image>,
-    green_trimmed_wall: Handle<Image>,
-    limestone_wall: Handle<Image>,
-    eggshell_wall: Handle<Image>,
-    barrier: Handle<Image>,
-    concrete: Handle<Image>,
-    ceiling_panel: Handle<Image>,
-    wood_slat_roof: Handle<Image>,
-    sky: Handle<Image>,
-    tombstone: Handle<Image>,
-    protest_image: Handle<Image>,
-    art: Handle<Image>,
-    mlk: Handle<Image>,
-    mlk_speech: Handle<AudioSource>,
-    headphones: Handle<Image>,
-    teacher_shirt: Handle<Image>,
-    velvet: Handle<Image>,
-    news: Handle<Image>,
-    josh_exit: Handle<Image>,
-    matt_exit: Handle<Image>,
-    ben_exit: Handle<Image>,
-    josh_sources: Handle<Image>,
-    matt_sources: Handle<Image>,
-    ben_sources: Handle<Image>,
-    intro_wall: Handle<Image>,
-    other_intro_wall: Handle<Image>,
-    reagan: Handle<Image>,
-    reagan_audio: Handle<AudioSource>,
-    cesar_chavez: Handle<Image>,
-    protestors: Handle<Image>,
-    modern_protestors: Handle<Image>,
-    works_cited: Handle<Image>,
-    //job_iden: Handle<Image>,
+    /// Textures loaded from the `assets/textures` folder
+    textures: HashMap<&'static str, Handle<Image>>,
+    /// High-resolution versions of textures shown by the image viewer, keyed by the texture's name
+    images: HashMap<&'static str, Handle<Image>>,
+    /// Audio tracks loaded from the `assets/sound` folder
+    audio: HashMap<&'static str, Handle<AudioSource>>,
+    /// First scene of every glTF model loaded from the `assets/models` folder
+    models: HashMap<&'static str, Handle<Scene>>,
+    /// Layout of the museum's walls and floors
+    layout: Handle<Layout>,
+    /// Tombstone text of every exhibit
+    catalog: Handle<Catalog>,
+    /// Checkerboard shown in place of textures that failed to load
+    placeholder: Handle<Image>,
 }
 
-/// Load all textures and set their repeat mode
+impl GlobalResources {
+    /// Ids of every asset that has to load before the museum is shown
+    pub fn handles(&self) -> Vec<HandleId> {
+        self.textures
+            .values()
+            .map(|texture| texture.id)
+            .chain(self.images.values().map(|image| image.id))
+            .chain(self.audio.values().map(|audio| audio.id))
+            .chain(self.models.values().map(|model| model.id))
+            .chain([self.layout.id, self.catalog.id])
+            .collect()
+    }
+}
+
+/// Names of all textures in the `assets/textures` folder, which are loaded from `<name>.png`
+const TEXTURES: &[&str] = &[
+    "birch-floor",
+    "blue-trimmed-wall",
+    "red-trimmed-wall",
+    "ceiling-panel",
+    "sky",
+    "flagstone-floor",
+    "limestone-wall",
+    "wood-slat-roof",
+    "eggshell-wall",
+    "linoleum-floor",
+    "concrete",
+    "oak-floor",
+    "tile-floor",
+    "green-trimmed-wall",
+    "red-tile-floor",
+    //"job-iden",
+    "barrier",
+    "tombstone",
+    "protest-image",
+    "art",
+    "martin-luther-king-jr",
+    "headphones",
+    "starbucks",
+    "velvet",
+    "news",
+    "josh-exit",
+    "matt-exit",
+    "ben-exit",
+    "intro-wall",
+    "reagan",
+    "cesar",
+    "other-intro",
+    "protestors",
+    "works-cited",
+    "modern-protestors",
+    "josh-exit-sources",
+    "matt-exit-sources",
+    "ben-exit-sources",
+];
+
+/// High-resolution versions of textures loaded from the `assets` folder, with the name of the
+/// texture they replace in the image viewer. They are given [image_sampler] instead of
+/// [texture_sampler]
+const IMAGES: &[(&str, &str)] = &[];
+
+/// All audio tracks loaded from the `assets` folder, with the name they are referred to by
+const AUDIO: &[(&str, &str)] = &[
+    ("mlk-speech", "sound/mlk-speech.ogg"),
+    ("reagan", "sound/reagan.ogg"),
+];
+
+/// Names of all glTF models in the `assets/models` folder, which are loaded from `<name>.gltf`
+const MODELS: &[&str] = &[
+    "dinner-pail",
+];
+
+/// Start loading all textures, audio, models and data files. Textures are shown once they have loaded
+/// and been given the museum's sampler by [configure_textures]
 fn load_resources(
     mut images: ResMut<Assets<Image>>,
     asset_server: Res<AssetServer>,
     mut resources: ResMut<GlobalResources>
 ) {
-    let mut load = |buf: &[u8]| {
-        let mut image = Image::from_buffer(
-            buf,
-            ImageType::MimeType("image/png"),
-            CompressedImageFormats::empty(),
-            true,
-        )
-        .expect("Failed to load texture");
-        image.sampler_descriptor.mag_filter = FilterMode::Nearest;
-        image.sampler_descriptor.min_filter = FilterMode::Nearest;
-        image.sampler_descriptor.address_mode_u = AddressMode::Repeat;
-        image.sampler_descriptor.address_mode_v = AddressMode::Repeat;
-        image.sampler_descriptor.address_mode_w = AddressMode::Repeat;
-
-        images.add(image)
-    };
-
-    resources.birch_floor = load(include_bytes!("../ct-assets/birch-floor.png"));
-    resources.blue_trimmed_wall = load(include_bytes!("../ct-assets/blue-trimmed-wall.png"));
-    resources.red_trimmed_wall = load(include_bytes!("../ct-assets/red-trimmed-wall.png"));
-    resources.ceiling_panel = load(include_bytes!("../ct-assets/ceiling-panel.png"));
-    resources.sky = load(include_bytes!("../ct-assets/sky.png"));
-    resources.flagstone_floor = load(include_bytes!("../ct-assets/flagstone-floor.png"));
-    resources.limestone_wall = load(include_bytes!("../ct-assets/limestone-wall.png"));
-    resources.wood_slat_roof = load(include_bytes!("../ct-assets/wood-slat-roof.png"));
-    resources.eggshell_wall = load(include_bytes!("../ct-assets/eggshell-wall.png"));
-    resources.linoleum_floor = load(include_bytes!("../ct-assets/linoleum-floor.png"));
-    resources.concrete = load(include_bytes!("../ct-assets/concrete.png"));
-    resources.oak_floor = load(include_bytes!("../ct-assets/oak-floor.png"));
-    resources.tile_floor = load(include_bytes!("../ct-assets/tile-floor.png"));
-    resources.green_trimmed_wall = load(include_bytes!("../ct-assets/green-trimmed-wall.png"));
-    resources.red_tile_floor = load(include_bytes!("../ct-assets/red-tile-floor.png"));
-    //resources.job_iden = load(include_bytes!("../assets/job-iden.png"));
-    resources.barrier = load(include_bytes!("../ct-assets/barrier.png"));
-    resources.tombstone = load(include_bytes!("../ct-assets/tombstone.png"));
-    resources.protest_image = load(include_bytes!("../ct-assets/protest-image.png"));
-    resources.art = load(include_bytes!("../ct-assets/art.png"));
-    resources.mlk = load(include_bytes!("../ct-assets/martin-luther-king-jr.png"));
-    resources.headphones = load(include_bytes!("../ct-assets/headphones.png"));
-    resources.teacher_shirt = load(include_bytes!("../ct-assets/starbucks.png"));
-    resources.velvet = load(include_bytes!("../ct-assets/velvet.png"));
-    resources.news = load(include_bytes!("../ct-assets/news.png"));
-    resources.josh_exit = load(include_bytes!("../ct-assets/josh-exit.png"));
-    resources.matt_exit = load(include_bytes!("../ct-assets/matt-exit.png"));
-    resources.ben_exit = load(include_bytes!("../ct-assets/ben-exit.png"));
-    resources.intro_wall = load(include_bytes!("../ct-assets/intro-wall.png"));
-    resources.reagan = load(include_bytes!("../ct-assets/reagan.png"));
-    resources.cesar_chavez = load(include_bytes!("../ct-assets/cesar.png"));
-    resources.other_intro_wall = load(include_bytes!("../ct-assets/other-intro.png"));
-    resources.protestors = load(include_bytes!("../ct-assets/protestors.png"));
-    resources.works_cited = load(include_bytes!("../ct-assets/works-cited.png"));
-    resources.modern_protestors = load(include_bytes!("../ct-assets/modern-protestors.png"));
-    resources.josh_sources = load(include_bytes!("../ct-assets/josh-exit-sources.png"));
-    resources.matt_sources = load(include_bytes!("../ct-assets/matt-exit-sources.png"));
-    resources.ben_sources = load(include_bytes!("../ct-assets/ben-exit-sources.png"));
-    
-    resources.mlk_speech = asset_server.load("sound/mlk-speech.ogg");
-    resources.reagan_audio = asset_server.load("sound/reagan.ogg");
+    resources.placeholder = images.add(checkerboard());
+
+    for name in TEXTURES {
+        resources.textures.insert(name, asset_server.load(&format!("textures/{}.png", name)));
+    }
+    for (name, path) in IMAGES {
+        resources.images.insert(name, asset_server.load(*path));
+    }
+    for (name, path) in AUDIO {
+        resources.audio.insert(name, asset_server.load(*path));
+    }
+    for name in MODELS {
+        resources.models.insert(name, asset_server.load(&format!("models/{}.gltf#Scene0", name)));
+    }
+
+    resources.layout = asset_server.load("museum.layout.ron");
+    resources.catalog = asset_server.load("museum.catalog.ron");
+}
+
+/// Sampler used by every texture, which repeats the texture without smoothing its pixels
+pub fn texture_sampler() -> SamplerDescriptor<'static> {
+    SamplerDescriptor {
+        mag_filter: FilterMode::Nearest,
+        min_filter: FilterMode::Nearest,
+        address_mode_u: AddressMode::Repeat,
+        address_mode_v: AddressMode::Repeat,
+        address_mode_w: AddressMode::Repeat,
+        ..default()
+    }
+}
+
+/// Sampler used by images in the image viewer, which smooths their pixels and doesn't repeat them
+pub fn image_sampler() -> SamplerDescriptor<'static> {
+    SamplerDescriptor {
+        mag_filter: FilterMode::Linear,
+        min_filter: FilterMode::Linear,
+        ..default()
+    }
+}
+
+/// Magenta and black checkerboard with [texture_sampler], two squares wide on every tile
+fn checkerboard() -> Image {
+    const MAGENTA: [u8; 4] = [255, 0, 255, 255];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+    let mut image = Image::new(
+        Extent3d { width: 2, height: 2, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        [MAGENTA, BLACK, BLACK, MAGENTA].concat(),
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.sampler_descriptor = texture_sampler();
+    image
+}
+
+/// Apply [texture_sampler] to textures and [image_sampler] to the image viewer's images whenever
+/// they finish loading or are reloaded
+fn configure_textures(
+    mut events: EventReader<AssetEvent<Image>>,
+    mut images: ResMut<Assets<Image>>,
+    resources: Res<GlobalResources>,
+) {
+    for event in events.iter() {
+        let handle = match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle,
+            AssetEvent::Removed { .. } => continue,
+        };
+        // Other images such as font atlases keep their own sampler
+        let sampler = if resources.textures.values().any(|texture| texture == handle) {
+            texture_sampler()
+        } else if resources.images.values().any(|image| image == handle) {
+            image_sampler()
+        } else {
+            continue
+        };
+        // Changing the sampler sends another modified event, so only change it if it isn't already set
+        let unconfigured = images
+            .get(handle)
+            .is_some_and(|image| image.sampler_descriptor != sampler);
+        if unconfigured {
+            if let Some(image) = images.get_mut(handle) {
+                image.sampler_descriptor = sampler;
+            }
+        }
+    }
 }
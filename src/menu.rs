@@ -0,0 +1,339 @@
+use bevy::app::AppExit;
+
+use super::*;
+use super::settings::SettingsSource;
+
+/// Colour shown over the museum while it is paused
+const BACKGROUND: Color = Color::rgba(0., 0., 0., 0.6);
+/// Colour of the panel that the menu's buttons are on
+const PANEL: Color = Color::rgb(0.08, 0.08, 0.08);
+/// Colour of a button
+const BUTTON: Color = Color::rgb(0.18, 0.18, 0.18);
+/// Colour of a button under the cursor
+const BUTTON_HOVERED: Color = Color::rgb(0.32, 0.32, 0.32);
+
+/// Marker component for the root node of the pause menu
+#[derive(Component)]
+pub struct PauseMenu;
+
+/// Page of the pause menu being shown
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MenuPage {
+    #[default]
+    Main,
+    Settings,
+    Controls,
+}
+
+/// What a button in the pause menu does when clicked
+#[derive(Component, Clone, Copy)]
+pub enum MenuButton {
+    Resume,
+    Open(MenuPage),
+    Quit,
+    InvertY,
+    HeadBob,
+    /// Change the field of view by this many degrees
+    Fov(f32),
+    /// Change the sensitivity by this much
+    Sensitivity(f32),
+    /// Change the smoothing by this much
+    Smoothing(f32),
+    /// Change the walking speed by this many metres per second
+    WalkSpeed(f32),
+}
+
+/// Open the pause menu when Escape is pressed. The image viewer is closed by Escape instead, as it
+/// is drawn over everything else
+pub(crate) fn pause(
+    mut kb: ResMut<Input<KeyCode>>,
+    mut state: ResMut<State<AppState>>,
+    players: Query<&Player>,
+) {
+    if !kb.just_pressed(KeyCode::Escape) || players.iter().any(|player| player.viewed_image.is_some()) {
+        return
+    }
+    // Otherwise the menu would see the same press and close again
+    kb.reset(KeyCode::Escape);
+    let _ = state.push(AppState::Paused);
+}
+
+/// Free the cursor while the museum is paused
+pub fn release_cursor(mut windows: ResMut<Windows>) {
+    if let Some(win) = windows.get_primary_mut() {
+        win.set_cursor_lock_mode(false);
+        win.set_cursor_visibility(true);
+    }
+}
+
+/// Start the pause menu on its main page, which [menu] then spawns
+pub fn open_menu(mut page: ResMut<MenuPage>) {
+    *page = MenuPage::Main;
+}
+
+/// Remove the pause menu once the museum is resumed
+pub fn close_menu(mut commands: Commands, menus: Query<Entity, With<PauseMenu>>) {
+    for menu in menus.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
+
+/// Handle clicks on the pause menu's buttons and Escape, rebuilding the menu whenever its page or
+/// the settings it shows change
+pub fn menu(
+    mut commands: Commands,
+    mut kb: ResMut<Input<KeyCode>>,
+    mut state: ResMut<State<AppState>>,
+    mut page: ResMut<MenuPage>,
+    mut settings: ResMut<Settings>,
+    mut source: ResMut<SettingsSource>,
+    mut buttons: Query<(&Interaction, &MenuButton, &mut UiColor), Changed<Interaction>>,
+    menus: Query<Entity, With<PauseMenu>>,
+    asset_server: Res<AssetServer>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if kb.just_pressed(KeyCode::Escape) {
+        kb.reset(KeyCode::Escape);
+        match *page {
+            MenuPage::Main => {
+                let _ = state.pop();
+                return
+            },
+            _ => *page = MenuPage::Main,
+        }
+    }
+
+    let mut edited = false;
+    for (interaction, button, mut color) in buttons.iter_mut() {
+        *color = UiColor(match interaction {
+            Interaction::Hovered => BUTTON_HOVERED,
+            _ => BUTTON,
+        });
+        if *interaction != Interaction::Clicked {
+            continue
+        }
+
+        edited |= !matches!(button, MenuButton::Resume | MenuButton::Open(_) | MenuButton::Quit);
+        match *button {
+            MenuButton::Resume => {
+                let _ = state.pop();
+                return
+            },
+            MenuButton::Open(to) => *page = to,
+            MenuButton::Quit => exit.send(AppExit),
+            MenuButton::InvertY => settings.invert_y = !settings.invert_y,
+            MenuButton::HeadBob => settings.head_bob = !settings.head_bob,
+            MenuButton::Fov(by) => settings.fov += by,
+            MenuButton::Sensitivity(by) => settings.sensitivity += by,
+            MenuButton::Smoothing(by) => settings.smoothing += by,
+            MenuButton::WalkSpeed(by) => settings.walk_speed += by,
+        }
+    }
+    if edited {
+        *settings = settings.clone().sanitized();
+        settings::save(&settings, &mut source);
+    }
+
+    if page.is_changed() || settings.is_changed() || menus.is_empty() {
+        for menu in menus.iter() {
+            commands.entity(menu).despawn_recursive();
+        }
+        spawn_menu(&mut commands, *page, &settings, asset_server.get_handle("fonts/times-new-roman.ttf"));
+    }
+}
+
+/// Spawn the pause menu showing the given page over the museum
+fn spawn_menu(commands: &mut Commands, page: MenuPage, settings: &Settings, font: Handle<Font>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: UiColor(BACKGROUND),
+            ..default()
+        })
+        .insert(PauseMenu)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        // Columns run bottom to top unless they are reversed
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::Center,
+                        padding: Rect::all(Val::Px(24.)),
+                        ..default()
+                    },
+                    color: UiColor(PANEL),
+                    ..default()
+                })
+                .with_children(|panel| {
+                    let menu = MenuBuilder { panel, font };
+                    match page {
+                        MenuPage::Main => menu.main(),
+                        MenuPage::Settings => menu.settings(settings),
+                        MenuPage::Controls => menu.controls(settings),
+                    }
+                });
+        });
+}
+
+/// Adds the contents of a page to the pause menu's panel
+struct MenuBuilder<'p, 'w, 's, 'a> {
+    panel: &'p mut ChildBuilder<'w, 's, 'a>,
+    font: Handle<Font>,
+}
+
+impl MenuBuilder<'_, '_, '_, '_> {
+    fn main(mut self) {
+        self.title("Paused");
+        self.button("Resume", MenuButton::Resume);
+        self.button("Settings", MenuButton::Open(MenuPage::Settings));
+        self.button("Controls", MenuButton::Open(MenuPage::Controls));
+        // Closing the tab is the only way to leave on the web
+        #[cfg(not(target_arch = "wasm32"))]
+        self.button("Quit", MenuButton::Quit);
+    }
+
+    fn settings(mut self, settings: &Settings) {
+        let on_off = |on: bool| if on { "On" } else { "Off" };
+
+        self.title("Settings");
+        self.button(format!("Invert Y: {}", on_off(settings.invert_y)), MenuButton::InvertY);
+        self.button(format!("Head bob: {}", on_off(settings.head_bob)), MenuButton::HeadBob);
+        self.adjust(format!("Field of view: {:.0}", settings.fov), MenuButton::Fov(-5.), MenuButton::Fov(5.));
+        self.adjust(
+            format!("Sensitivity: {:.1}", settings.sensitivity),
+            MenuButton::Sensitivity(-0.1),
+            MenuButton::Sensitivity(0.1),
+        );
+        self.adjust(
+            format!("Smoothing: {:.1}", settings.smoothing),
+            MenuButton::Smoothing(-0.1),
+            MenuButton::Smoothing(0.1),
+        );
+        self.adjust(
+            format!("Walk speed: {:.0}", settings.walk_speed),
+            MenuButton::WalkSpeed(-1.),
+            MenuButton::WalkSpeed(1.),
+        );
+        self.button("Back", MenuButton::Open(MenuPage::Main));
+    }
+
+    fn controls(mut self, settings: &Settings) {
+        let keys = &settings.keys;
+        let key = |key: KeyCode| format!("[{}]", key_name(key));
+
+        self.title("Controls");
+        self.text(format!(
+            "{} {} {} {}  Walk",
+            key(keys.forward),
+            key(keys.left),
+            key(keys.back),
+            key(keys.right),
+        ));
+        self.text("Mouse  Look around");
+        self.text(format!("{}  Read, listen to, view or inspect an exhibit, and leave it", key(keys.interact)));
+        self.text("Mouse and wheel  Turn and zoom models, pan and zoom images");
+        self.text("[escape]  Pause");
+        self.text("Keys can be changed in the settings file");
+        self.button("Back", MenuButton::Open(MenuPage::Main));
+    }
+
+    fn style(&self, size: f32) -> TextStyle {
+        TextStyle {
+            font: self.font.clone(),
+            font_size: size,
+            color: Color::WHITE,
+        }
+    }
+
+    fn title(&mut self, title: &str) {
+        let style = self.style(36.);
+        self.panel.spawn_bundle(TextBundle {
+            style: Style {
+                margin: Rect { bottom: Val::Px(16.), ..default() },
+                ..default()
+            },
+            text: Text::with_section(title, style, default()),
+            ..default()
+        });
+    }
+
+    fn text(&mut self, text: impl Into<String>) {
+        let style = self.style(20.);
+        self.panel.spawn_bundle(TextBundle {
+            style: Style {
+                margin: Rect::all(Val::Px(4.)),
+                ..default()
+            },
+            text: Text::with_section(text, style, default()),
+            ..default()
+        });
+    }
+
+    fn button(&mut self, label: impl Into<String>, action: MenuButton) {
+        let style = self.style(24.);
+        spawn_button(self.panel, label.into(), action, style, 280.);
+    }
+
+    /// A label between buttons that lower and raise the value it shows
+    fn adjust(&mut self, label: String, lower: MenuButton, raise: MenuButton) {
+        let (style, label_style) = (self.style(24.), self.style(22.));
+        self.panel
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                color: UiColor(Color::NONE),
+                ..default()
+            })
+            .with_children(|row| {
+                spawn_button(row, "-".to_owned(), lower, style.clone(), 44.);
+                row.spawn_bundle(TextBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(180.), Val::Auto),
+                        margin: Rect::all(Val::Px(4.)),
+                        ..default()
+                    },
+                    text: Text::with_section(
+                        label,
+                        label_style,
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..default()
+                        },
+                    ),
+                    ..default()
+                });
+                spawn_button(row, "+".to_owned(), raise, style, 44.);
+            });
+    }
+}
+
+/// Spawn a button with a centred label
+fn spawn_button(parent: &mut ChildBuilder, label: String, action: MenuButton, style: TextStyle, width: f32) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(width), Val::Px(44.)),
+                margin: Rect::all(Val::Px(6.)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            color: UiColor(BUTTON),
+            ..default()
+        })
+        .insert(action)
+        .with_children(|button| {
+            button.spawn_bundle(TextBundle {
+                text: Text::with_section(label, style, default()),
+                ..default()
+            });
+        });
+}
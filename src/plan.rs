@@ -0,0 +1,211 @@
+use std::fmt;
+
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+use super::*;
+
+/// A position on the floor plan
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum Point {
+    /// The position of a named vertex
+    Vertex(String),
+    /// A position offset along the X and Z axes from a named vertex
+    Offset(String, f32, f32),
+    /// An absolute position
+    Absolute(f32, f32),
+}
+
+impl From<&str> for Point {
+    fn from(name: &str) -> Self {
+        Self::Vertex(name.to_owned())
+    }
+}
+
+impl<X: Into<f32>, Z: Into<f32>> From<(X, Z)> for Point {
+    fn from((x, z): (X, Z)) -> Self {
+        Self::Absolute(x.into(), z.into())
+    }
+}
+
+impl From<Vec2> for Point {
+    fn from(pos: Vec2) -> Self {
+        Self::Absolute(pos.x, pos.y)
+    }
+}
+
+/// A line between two points of the floor plan that a wall is placed along
+#[derive(Clone, Debug)]
+pub struct Edge {
+    /// Name of the element placed along this edge
+    pub name: String,
+    pub from: Point,
+    pub to: Point,
+}
+
+/// Table of named vertices and the edges between them that make up the museum's walls
+#[derive(Default)]
+pub struct FloorPlan {
+    /// All named vertices
+    vertices: HashMap<String, Vec2>,
+    /// Names of vertices that were defined more than once
+    duplicates: Vec<String>,
+    /// Edges between vertices, one for every wall in the scene
+    edges: Vec<Edge>,
+}
+
+/// Problem found while validating a floor plan
+#[derive(Debug)]
+pub enum PlanError {
+    /// A vertex name was defined more than once
+    DuplicateVertex(String),
+    /// An element references a vertex that is not in the vertex table
+    UnknownVertex {
+        element: String,
+        vertex: String,
+    },
+    /// An edge begins and ends at the same position
+    ZeroLength(String),
+    /// A room's corners don't enclose any area
+    NoArea(String),
+    /// A floor's outline or one of its holes has fewer than three points
+    TooFewPoints(String),
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DuplicateVertex(name) => write!(f, "vertex '{}' is defined more than once", name),
+            Self::UnknownVertex { element, vertex } => write!(f, "'{}' references unknown vertex '{}'", element, vertex),
+            Self::ZeroLength(element) => write!(f, "wall '{}' has zero length", element),
+            Self::NoArea(element) => write!(f, "room '{}' doesn't enclose any area", element),
+            Self::TooFewPoints(element) => {
+                write!(f, "floor '{}' has an outline or hole with fewer than 3 points", element)
+            },
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+impl FloorPlan {
+    /// Create a floor plan with no vertices or edges
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a named vertex to the plan
+    pub fn with_vertex(mut self, name: impl Into<String>, pos: (f32, f32)) -> Self {
+        self.add_vertex(name, pos);
+        self
+    }
+
+    /// Add a named vertex to the plan, recording an error if the name is already taken
+    pub fn add_vertex(&mut self, name: impl Into<String>, (x, z): (f32, f32)) {
+        let name = name.into();
+        if self.vertices.insert(name.clone(), Vec2::new(x, z)).is_some() {
+            self.duplicates.push(name);
+        }
+    }
+
+    /// Add an edge that a wall is placed along
+    pub fn add_edge(&mut self, edge: Edge) {
+        self.edges.push(edge);
+    }
+
+    /// Get all edges in the plan
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    /// Resolve a point to a position in the world, `element` is used to report which element
+    /// referenced an unknown vertex
+    pub fn point(&self, element: &str, point: &Point) -> Result<Vec2, PlanError> {
+        let vertex = |name: &String| self.vertices
+            .get(name)
+            .copied()
+            .ok_or_else(|| PlanError::UnknownVertex {
+                element: element.to_owned(),
+                vertex: name.clone(),
+            });
+
+        match point {
+            Point::Vertex(name) => vertex(name),
+            Point::Offset(name, x, z) => Ok(vertex(name)? + Vec2::new(*x, *z)),
+            Point::Absolute(x, z) => Ok(Vec2::new(*x, *z)),
+        }
+    }
+
+    /// Resolve both ends of an edge, rejecting edges that have no length
+    pub fn segment(&self, edge: &Edge) -> Result<(Vec2, Vec2), PlanError> {
+        let from = self.point(&edge.name, &edge.from)?;
+        let to = self.point(&edge.name, &edge.to)?;
+        match from.distance(to) <= f32::EPSILON {
+            true => Err(PlanError::ZeroLength(edge.name.clone())),
+            false => Ok((from, to)),
+        }
+    }
+
+    /// Check that no vertex was defined twice and that every edge is between known vertices and
+    /// has a length
+    pub fn validate(&self) -> Result<(), PlanError> {
+        if let Some(name) = self.duplicates.first() {
+            return Err(PlanError::DuplicateVertex(name.clone()))
+        }
+        self.edges
+            .iter()
+            .try_for_each(|edge| self.segment(edge).map(drop))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(name: &str, from: impl Into<Point>, to: impl Into<Point>) -> Edge {
+        Edge {
+            name: name.to_owned(),
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    fn plan() -> FloorPlan {
+        FloorPlan::new()
+            .with_vertex("a", (0., 0.))
+            .with_vertex("b", (4., 0.))
+    }
+
+    #[test]
+    fn valid_plan_is_accepted() {
+        let mut plan = plan();
+        plan.add_edge(edge("a-b", "a", "b"));
+        plan.add_edge(edge("offset", Point::Offset("a".to_owned(), 0., 1.), (4., 1.)));
+        assert!(plan.validate().is_ok());
+        assert_eq!(plan.point("offset", &Point::Offset("b".to_owned(), 1., 2.)).unwrap(), Vec2::new(5., 2.));
+    }
+
+    #[test]
+    fn duplicate_vertex_is_rejected() {
+        let plan = plan().with_vertex("a", (1., 1.));
+        assert!(matches!(plan.validate(), Err(PlanError::DuplicateVertex(name)) if name == "a"));
+    }
+
+    #[test]
+    fn unknown_vertex_is_rejected() {
+        let mut plan = plan();
+        plan.add_edge(edge("a-c", "a", Point::Offset("c".to_owned(), 1., 0.)));
+        assert!(matches!(
+            plan.validate(),
+            Err(PlanError::UnknownVertex { element, vertex }) if element == "a-c" && vertex == "c"
+        ));
+    }
+
+    #[test]
+    fn zero_length_edge_is_rejected() {
+        let mut plan = plan();
+        plan.add_edge(edge("b-b", Point::Offset("a".to_owned(), 4., 0.), "b"));
+        assert!(matches!(plan.validate(), Err(PlanError::ZeroLength(name)) if name == "b-b"));
+    }
+}
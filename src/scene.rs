@@ -1,26 +1,47 @@
+use bevy::{asset::LoadState, scene::SpawnSceneAsChildCommands};
+
 use super::*;
+use super::plan::*;
 
 
 /// Structure for constructing the map
 pub struct SceneBuilder {
+    /// Named vertices that walls and floors are placed between
+    plan: FloorPlan,
     /// All walls in the scene
     walls: Vec<WallBuilder>,
     /// All floors / ceilings in the scene
     floors: Vec<FloorBuilder>,
+    /// All rooms in the scene
+    rooms: Vec<RoomBuilder>,
+    /// All exhibits in the scene
+    exhibits: Vec<ExhibitBuilder>,
+    /// All boxes in the scene
+    boxes: Vec<BoxBuilder>,
+    /// All models on pedestals in the scene
+    artifacts: Vec<ArtifactBuilder>,
 }
 
 impl SceneBuilder {
-    /// Create a new scene builder that contains all data needed to add walls and floors to the
-    /// scene
-    pub fn new() -> Self {
+    /// Create a new scene builder that places walls and floors on the given floor plan
+    pub fn new(plan: FloorPlan) -> Self {
         Self {
+            plan,
             walls: vec![],
             floors: vec![],
+            rooms: vec![],
+            exhibits: vec![],
+            boxes: vec![],
+            artifacts: vec![],
         }
     }
 
-    /// Add a wall to this scene
-    pub fn with_wall(mut self, wall: WallBuilder) -> Self {
+    /// Add a wall to this scene, adding its edge to the floor plan
+    pub fn with_wall(mut self, mut wall: WallBuilder) -> Self {
+        if wall.edge.name.is_empty() {
+            wall.edge.name = format!("wall #{}", self.walls.len());
+        }
+        self.plan.add_edge(wall.edge.clone());
         self.walls.push(wall);
         self
     }
@@ -31,26 +52,183 @@ impl SceneBuilder {
         self
     }
 
-    /// Finish building the scene and add all walls and floors
+    /// Add a room to this scene, adding an edge to the floor plan for each of its sides
+    pub fn with_room(mut self, mut room: RoomBuilder) -> Self {
+        if room.name.is_empty() {
+            room.name = format!("room #{}", self.rooms.len());
+        }
+        for (i, side) in room.sides.iter_mut().enumerate() {
+            side.edge.name = format!("{} side {}", room.name, i);
+            self.plan.add_edge(side.edge.clone());
+        }
+        self.rooms.push(room);
+        self
+    }
+
+    /// Add an exhibit to this scene, adding the edge of its host wall to the floor plan
+    pub fn with_exhibit(mut self, mut exhibit: ExhibitBuilder) -> Self {
+        if exhibit.host.name.is_empty() {
+            exhibit.host.name = format!("exhibit #{}", self.exhibits.len());
+        }
+        self.plan.add_edge(exhibit.host.clone());
+        self.exhibits.push(exhibit);
+        self
+    }
+
+    /// Add a box to this scene
+    pub fn with_box(mut self, mut bx: BoxBuilder) -> Self {
+        if bx.name.is_empty() {
+            bx.name = format!("box #{}", self.boxes.len());
+        }
+        self.boxes.push(bx);
+        self
+    }
+
+    /// Add a model on a pedestal to this scene
+    pub fn with_artifact(mut self, mut artifact: ArtifactBuilder) -> Self {
+        if artifact.pedestal.name.is_empty() {
+            artifact.pedestal.name = format!("artifact #{}", self.artifacts.len());
+        }
+        self.artifacts.push(artifact);
+        self
+    }
+
+    /// Check that the floor plan is valid and that the position of every element in the scene can
+    /// be resolved, which [SceneBuilder::finish] needs before it adds anything
+    pub fn validate(&self) -> Result<(), PlanError> {
+        self.plan.validate()?;
+        for floor in self.floors.iter() {
+            floor.outline(&self.plan)?;
+        }
+        for wall in self.walls.iter() {
+            self.plan.segment(&wall.edge)?;
+        }
+        for room in self.rooms.iter() {
+            let polygon = room.polygon
+                .iter()
+                .map(|corner| self.plan.point(&room.name, corner))
+                .collect::<Result<Vec<_>, PlanError>>()?;
+            room.outward_face(&polygon)?;
+        }
+        for exhibit in self.exhibits.iter() {
+            self.plan.segment(&exhibit.host)?;
+        }
+        for bx in self.boxes.iter() {
+            self.plan.point(&bx.name, &bx.position)?;
+        }
+        for artifact in self.artifacts.iter() {
+            self.plan.point(&artifact.pedestal.name, &artifact.pedestal.position)?;
+        }
+        Ok(())
+    }
+
+    /// Finish building the scene and add all walls, floors, rooms, exhibits, boxes and artifacts,
+    /// returning every spawned
+    /// entity. Geometry that shares a material is merged into a single mesh. Nothing is added if
+    /// the floor plan has duplicate vertices, or if any element references a vertex that doesn't
+    /// exist, is zero-length or is a floor with too few points
     pub fn finish<'w, 's>(
         self,
         commands: &mut Commands<'w, 's>,
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<StandardMaterial>>,
-    ) {
-        self.walls.iter().for_each(|wall| {
-            wall.build(commands, meshes, materials);
-        });
-        self.floors.iter().for_each(|floor| {
-            floor.build(commands, meshes, materials);
-        });
+    ) -> Result<Vec<Entity>, PlanError> {
+        self.validate()?;
+        let floors = self.floors
+            .iter()
+            .map(|floor| Ok((floor, floor.outline(&self.plan)?)))
+            .collect::<Result<Vec<_>, PlanError>>()?;
+
+        let walls = self.walls
+            .iter()
+            .map(|wall| Ok((wall, self.plan.segment(&wall.edge)?)))
+            .collect::<Result<Vec<_>, PlanError>>()?;
+
+        let rooms = self.rooms
+            .into_iter()
+            .map(|mut room| {
+                let polygon = room.polygon
+                    .iter()
+                    .map(|corner| self.plan.point(&room.name, corner))
+                    .collect::<Result<Vec<_>, PlanError>>()?;
+                room.face_inwards(&polygon)?;
+                Ok((room, polygon))
+            })
+            .collect::<Result<Vec<_>, PlanError>>()?;
+
+        let exhibits = self.exhibits
+            .iter()
+            .map(|exhibit| Ok((exhibit, self.plan.segment(&exhibit.host)?)))
+            .collect::<Result<Vec<_>, PlanError>>()?;
+
+        let boxes = self.boxes
+            .iter()
+            .map(|bx| Ok((bx, self.plan.point(&bx.name, &bx.position)?)))
+            .collect::<Result<Vec<_>, PlanError>>()?;
+
+        let artifacts = self.artifacts
+            .iter()
+            .map(|artifact| Ok((artifact, self.plan.point(&artifact.pedestal.name, &artifact.pedestal.position)?)))
+            .collect::<Result<Vec<_>, PlanError>>()?;
+
+        let mut batches = Batches::default();
+        let mut spawned = vec![];
+        for &(wall, (from, to)) in walls.iter() {
+            spawned.extend(wall.build(from, to, &mut batches, commands));
+        }
+        for (floor, (outline, holes)) in floors {
+            floor.build(&outline, &holes, &mut batches);
+        }
+        for (room, polygon) in rooms.iter() {
+            spawned.extend(room.build(polygon, &mut batches, commands));
+        }
+        // Exhibits hang on the wall or side of a room that runs between the same end points, in
+        // either direction
+        let hosts = walls
+            .iter()
+            .copied()
+            .chain(rooms.iter().flat_map(|(room, polygon)| room.sides
+                .iter()
+                .zip(polygon.iter().zip(polygon.iter().cycle().skip(1)))
+                .map(|(side, (&from, &to))| (side, (from, to)))
+            ))
+            .collect::<Vec<_>>();
+        for (exhibit, (from, to)) in exhibits {
+            let same = |a: Vec2, b: Vec2| a.distance(b) <= 1e-4;
+            let host = hosts
+                .iter()
+                .find(|(_, (a, b))| (same(*a, from) && same(*b, to)) || (same(*a, to) && same(*b, from)))
+                .copied();
+            spawned.extend(exhibit.build(from, to, host, &mut batches, commands));
+        }
+        for (bx, position) in boxes {
+            spawned.extend(bx.build(position, &mut batches, commands));
+        }
+        for (artifact, position) in artifacts {
+            spawned.extend(artifact.build(position, &mut batches, commands));
+        }
+        spawned.extend(batches.spawn(commands, meshes, materials));
+        Ok(spawned)
     }
 }
 
 pub const WALL_HEIGHT: f32 = 3.7;
 
+/// Way of calculating texture repetitions from the size of a wall or floor
+#[derive(Clone, Copy)]
+enum Autotile {
+    /// Repeat once per unit of length and height
+    Size,
+    /// Repeat once per two units of length
+    Length,
+    /// Repeat once per physical size of the texture in metres, lined up in world space
+    Density(Vec2),
+}
+
 /// Structure containing all data needed for a wall
 pub struct WallBuilder {
+    /// Name and end points of the wall
+    edge: Edge,
     /// If this wall has collision
     collision: bool,
     /// Height offset of the wall
@@ -59,16 +237,16 @@ pub struct WallBuilder {
     height: f32,
     /// Texture of the wall
     texture: Option<Handle<Image>>,
-    /// Color of the wall
-    color: Color,
-    /// Where the wall begins
-    from: Vec2,
-    /// Where the wall ends
-    to: Vec2,
+    /// Color of the wall, shaded by the wall's angle if not set
+    color: Option<Color>,
     /// How many times to repeat the applied texture in the X coordinate
     tiles_wide: f32,
     /// How many times to repeat the applied texture in the Y coordinate
     tiles_tall: f32,
+    /// Calculate texture repetitions from the wall's size once its position is known
+    autotile: Option<Autotile>,
+    /// Wether or not to flip the texture horizontally
+    mirror: bool,
     /// Wether or not to enable transparency
     transparent: bool,
     /// What side to cull, optional
@@ -77,14 +255,42 @@ pub struct WallBuilder {
     action: Option<InteractableAction>,
     /// What radius for the interactable action
     radius: f32,
+    /// Doorways cut out of the bottom of the wall
+    openings: Vec<Opening>,
+    /// Thickness of the wall, or 0 for a single quad
+    thickness: f32,
+    /// Quads placed on the faces of the wall
+    decals: Vec<DecalBuilder>,
 }
 
-/// A rectangle floor
+/// A gap in a wall that can be walked through
+#[derive(Clone, Copy)]
+struct Opening {
+    /// Distance along the wall from its first end point to the start of the opening
+    start: f32,
+    /// Length of the opening along the wall
+    width: f32,
+    /// Height of the top of the opening, above which the wall is kept
+    height: f32,
+}
+
+/// Outline of a floor, resolved to positions in the world when the floor is built
+enum FloorShape {
+    /// An axis-aligned rectangle between two opposite corners, textured from the first corner
+    Rect(Point, Point),
+    /// A polygon with holes cut out of it, textured in world space so neighbouring floors line up
+    Polygon {
+        outline: Vec<Point>,
+        holes: Vec<Vec<Point>>,
+    },
+}
+
+/// A rectangle or polygon floor
 pub struct FloorBuilder {
-    /// Position that the rectangle begins from
-    from: Vec2,
-    /// Position that the rectangle ends at
-    to: Vec2,
+    /// Name used to report errors in the floor's position
+    name: String,
+    /// Corners of the floor
+    shape: FloorShape,
     /// Texture of the floor, if any
     texture: Option<Handle<Image>>,
     /// Color of the floor
@@ -95,29 +301,67 @@ pub struct FloorBuilder {
     tiles_wide: f32,
     /// How many times to repeat the applied texture in the Y coordinate
     tiles_tall: f32,
+    /// Calculate texture repetitions from the floor's size once its position is known
+    autotile: bool,
+    /// Physical size of the texture in metres, lined up in world space
+    texture_size: Option<Vec2>,
+    /// Wether or not to flip the texture along the X axis
+    mirror: bool,
+    /// Wether or not to enable transparency
+    transparent: bool,
     /// What side to cull while rendering
     cull: Option<Face>,
 }
 
 impl FloorBuilder {
-    /// Create a new wall with height of 1, collision enabled, and untextured gray
-    pub fn new(
-        from: (impl Into<f32>, impl Into<f32>),
-        to: (impl Into<f32>, impl Into<f32>),
-    ) -> Self {
-        let from = Vec2::new(from.0.into(), from.1.into());
-        let to = Vec2::new(to.0.into(), to.1.into());
+    /// Create a new rectangle floor at height 0, untextured gray
+    pub fn new(from: impl Into<Point>, to: impl Into<Point>) -> Self {
+        Self::with_shape(FloorShape::Rect(from.into(), to.into()))
+    }
+
+    /// Create a new polygon floor at height 0, untextured gray. The polygon can be concave, and its
+    /// front face points up. Its texture repeats once every two units in world space
+    pub fn polygon<P: Into<Point>>(outline: impl IntoIterator<Item = P>) -> Self {
+        Self::with_shape(FloorShape::Polygon {
+            outline: outline.into_iter().map(Into::into).collect(),
+            holes: vec![],
+        })
+    }
+
+    fn with_shape(shape: FloorShape) -> Self {
         Self {
+            name: String::from("floor"),
+            shape,
             height: 0.,
             texture: None,
             color: Color::default() * 0.7,
-            from,
-            to,
             tiles_wide: 1.,
             tiles_tall: 1.,
+            autotile: false,
+            texture_size: None,
+            mirror: false,
+            transparent: false,
             cull: None,
         }
     }
+
+    /// Set the name used to report errors in this floor's position
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Cut a hole out of a polygon floor. Holes must be inside the floor and not overlap
+    ///
+    /// # Panics
+    /// If this floor is a rectangle
+    pub fn with_hole<P: Into<Point>>(mut self, hole: impl IntoIterator<Item = P>) -> Self {
+        match &mut self.shape {
+            FloorShape::Polygon { holes, .. } => holes.push(hole.into_iter().map(Into::into).collect()),
+            FloorShape::Rect(..) => panic!("holes can only be cut out of polygon floors"),
+        }
+        self
+    }
     
     /// Set what side to cull while rendering
     pub fn with_cull(mut self, cull: Face) -> Self {
@@ -130,17 +374,38 @@ impl FloorBuilder {
         self.with_color(Color::rgb(brightness, brightness, brightness))
     }
 
-    /// Set how many times to repeat the applied texture in X and Y coordinates
+    /// Set how many times to repeat the applied texture in X and Y coordinates of a rectangle
     pub fn with_tiles(mut self, width: f32, height: f32) -> Self {
         self.tiles_wide = width;
         self.tiles_tall = height;
+        self.autotile = false;
+        self.texture_size = None;
         self
     }
 
-    /// Calculate texture repetitions based on size of the floor
+    /// Calculate texture repetitions based on size of a rectangle floor
     pub fn autotile(mut self) -> Self {
-        self.tiles_wide = (self.from - self.to).x.abs() / 2.;
-        self.tiles_tall = (self.from - self.to).y.abs() / 2.;
+        self.autotile = true;
+        self.texture_size = None;
+        self
+    }
+
+    /// Repeat the texture once every `width` metres along X and `height` metres along Z, for
+    /// either shape. Textures line up across floors, measured from the world's origin
+    pub fn with_texture_size(mut self, width: f32, height: f32) -> Self {
+        self.texture_size = Some(Vec2::new(width, height));
+        self
+    }
+
+    /// Flip the texture along the X axis
+    pub fn with_mirror(mut self, mirror: bool) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// Enable or disable transparency for this floor's color and texture
+    pub fn with_transparency(mut self, transparency: bool) -> Self {
+        self.transparent = transparency;
         self
     }
 
@@ -162,78 +427,115 @@ impl FloorBuilder {
         self
     }
 
-    /// Add the wall to the scene
-    pub fn build<'w, 's, 'a>(
-        &self,
-        commands: &'a mut Commands<'w, 's>,
-        meshes: &mut ResMut<Assets<Mesh>>,
-        materials: &mut ResMut<Assets<StandardMaterial>>,
-    ) -> EntityCommands<'w, 's, 'a> {
-        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
-        let verts = vec![
-            [self.from.x, self.height, self.from.y], //bl
-            [self.to.x, self.height, self.from.y],   //br
-            [self.from.x, self.height, self.to.y],   //tl
-            [self.to.x, self.height, self.to.y],     //tr
-        ];
+    /// Resolve the positions of the floor's outline and holes. A rectangle's outline begins at
+    /// `from` and goes around its corners, and polygons and their holes need at least 3 points
+    pub fn outline(&self, plan: &FloorPlan) -> Result<(Vec<Vec2>, Vec<Vec<Vec2>>), PlanError> {
+        let points = |points: &[Point]| match points.len() {
+            0..=2 => Err(PlanError::TooFewPoints(self.name.clone())),
+            _ => points
+                .iter()
+                .map(|point| plan.point(&self.name, point))
+                .collect::<Result<Vec<_>, PlanError>>(),
+        };
 
+        match &self.shape {
+            FloorShape::Rect(from, to) => {
+                let (from, to) = (plan.point(&self.name, from)?, plan.point(&self.name, to)?);
+                Ok((vec![from, Vec2::new(to.x, from.y), to, Vec2::new(from.x, to.y)], vec![]))
+            },
+            FloorShape::Polygon { outline, holes } => Ok((
+                points(outline)?,
+                holes.iter().map(|hole| points(hole)).collect::<Result<_, _>>()?,
+            )),
+        }
+    }
 
-        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0., 0., 1.]; 4]);
-        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, verts);
-        mesh.insert_attribute(
-            Mesh::ATTRIBUTE_UV_0,
-            vec![
-                [0., 0.],
-                [self.tiles_wide, 0.],
-                [0., self.tiles_tall],
-                [self.tiles_wide, self.tiles_tall],
-            ],
-        );
+    /// Add the floor's geometry to the scene with the resolved positions of its outline and holes
+    pub fn build(&self, outline: &[Vec2], holes: &[Vec<Vec2>], batches: &mut Batches) {
+        let points = outline
+            .iter()
+            .chain(holes.iter().flatten())
+            .copied()
+            .collect::<Vec<_>>();
 
-        mesh.set_indices(Some(Indices::U16(vec![0, 2, 1, 3, 1, 2])));
-
-        let command = commands.spawn_bundle(PbrBundle {
-            mesh: meshes.add(mesh.clone()),
-            material: materials.add(StandardMaterial {
-                base_color: self.color,
-                base_color_texture: self.texture.clone(),
-                cull_mode: self.cull,
-                unlit: true,
-                ..Default::default()
-            }),
-            ..default()
-        });
+        // Corner and size of a rectangle, which has its four corners in order
+        let rect = match self.shape {
+            FloorShape::Rect(..) => Some((outline[0], outline[2] - outline[0])),
+            FloorShape::Polygon { .. } => None,
+        };
+        let uv = |p: Vec2| match (self.texture_size, rect) {
+            (Some(texture_size), _) => p / texture_size,
+            (None, Some((from, size))) => {
+                let tiles = match self.autotile {
+                    true => size.abs() / 2.,
+                    false => Vec2::new(self.tiles_wide, self.tiles_tall),
+                };
+                (p - from) / size * tiles
+            },
+            (None, None) => p / 2.,
+        };
+        let mirror = if self.mirror { Vec2::new(-1., 1.) } else { Vec2::ONE };
+        let uvs = points.iter().map(|p| (uv(*p) * mirror).to_array()).collect();
+        // Rectangles face up when their corners are given in the same order on both axes
+        let faces_up = rect.is_none_or(|(_, size)| size.x * size.y > 0.);
+
+        let mut ring = (0..outline.len() as u32).collect::<Vec<_>>();
+        let mut start = outline.len() as u32;
+        for hole in holes {
+            let hole_ring = (start..start + hole.len() as u32).collect();
+            ring = bridge(&points, ring, hole_ring);
+            start += hole.len() as u32;
+        }
+        // Triangles are wound counter-clockwise from above, which faces them down
+        let mut indices = triangulate(&points, ring);
+        if faces_up {
+            indices.chunks_mut(3).for_each(|triangle| triangle.swap(1, 2));
+        }
 
-        command
+        let geometry = Geometry {
+            positions: points.iter().map(|p| [p.x, self.height, p.y]).collect(),
+            normals: vec![[0., if faces_up { 1. } else { -1. }, 0.]; points.len()],
+            uvs,
+            indices,
+        };
+        batches.add(MaterialKey::new(self.color, &self.texture, self.cull, self.transparent), geometry);
     }
 }
 
 impl WallBuilder {
     /// Create a new wall with height of 1, collision enabled, and untextured gray
-    pub fn new(
-        from: (impl Into<f32>, impl Into<f32>),
-        to: (impl Into<f32>, impl Into<f32>),
-    ) -> Self {
-        let from = Vec2::new(from.0.into(), from.1.into());
-        let to = Vec2::new(to.0.into(), to.1.into());
-        let color = 0.4 + 0.2 * ((from - to).angle_between(Vec2::Y) / (std::f32::consts::PI * 2.)).abs();
+    pub fn new(from: impl Into<Point>, to: impl Into<Point>) -> Self {
         Self {
+            edge: Edge {
+                name: String::new(),
+                from: from.into(),
+                to: to.into(),
+            },
             collision: true,
             h_off: 0.,
             height: WALL_HEIGHT,
             texture: None,
-            color: Color::rgb(color, color, color),
-            from,
-            to,
+            color: None,
             tiles_tall: 1.,
             tiles_wide: 1.,
+            autotile: None,
+            mirror: false,
             transparent: false,
             cull: None,
             action: None,
             radius: 2.5,
+            openings: vec![],
+            thickness: 0.,
+            decals: vec![],
         }
     }
     
+    /// Set the name used to report errors in this wall's position
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.edge.name = name.into();
+        self
+    }
+
     /// Add a text entity to display for this wall when interacted
     pub fn with_action(mut self, action: InteractableAction) -> Self {
         self.action = Some(action);
@@ -250,30 +552,39 @@ impl WallBuilder {
     pub fn with_tiles(mut self, wide: f32, tall: f32) -> Self {
         self.tiles_wide = wide;
         self.tiles_tall = tall;
+        self.autotile = None;
         self
     }
 
     /// Calculate how many times to repeat the tile based on height and length of the wall
     pub fn autotile(mut self) -> Self {
-        self.tiles_wide = self.from.distance(self.to);
-        self.tiles_tall = self.height;
+        self.autotile = Some(Autotile::Size);
         self
     }
 
     /// Calculate how many times to repeat the texture based on length, while always using one
     /// tile's height for the height
     pub fn autotile_len(mut self) -> Self {
-        self.tiles_wide = self.from.distance(self.to) / 2.;
+        self.autotile = Some(Autotile::Length);
+        self
+    }
+
+    /// Repeat the texture once every `width` metres along the wall and `height` metres up it.
+    /// Textures line up across walls, measured from the world's origin and the ground
+    pub fn with_texture_size(mut self, width: f32, height: f32) -> Self {
+        self.autotile = Some(Autotile::Density(Vec2::new(width, height)));
+        self
+    }
+
+    /// Flip the texture horizontally
+    pub fn with_mirror(mut self, mirror: bool) -> Self {
+        self.mirror = mirror;
         self
     }
 
     /// Add a texture to this wall
     pub fn with_texture(mut self, texture: Handle<Image>) -> Self {
         self.texture = Some(texture);
-        let color = 0.6
-            + 0.2 * ((self.from - self.to).angle_between(Vec2::Y) / (std::f32::consts::PI * 2.)).abs();
-
-        self.color = Color::WHITE * color;
         self
     }
 
@@ -291,7 +602,7 @@ impl WallBuilder {
 
     /// Set the color of this wall
     pub fn with_color(mut self, color: Color) -> Self {
-        self.color = color;
+        self.color = Some(color);
         self
     }
 
@@ -313,69 +624,1277 @@ impl WallBuilder {
         self
     }
 
-    /// Add the wall to the scene
-    pub fn build<'w, 's, 'a>(
+    /// Build this wall as a box of the given thickness centred on its edge, with a face and
+    /// collider on both sides and on its ends. Thick walls can be seen from any side, so their cull
+    /// face is ignored
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Cut a doorway out of the bottom of this wall, beginning `start` units along the wall from
+    /// its first end point. The wall above the opening's height is kept as a lintel
+    pub fn with_opening(mut self, start: f32, width: f32, height: f32) -> Self {
+        self.openings.push(Opening { start, width, height });
+        self
+    }
+
+    /// Place a decal on one of this wall's faces
+    pub fn with_decal(mut self, decal: DecalBuilder) -> Self {
+        self.decals.push(decal);
+        self
+    }
+
+    /// Add the wall's geometry to the scene between the resolved positions of its end points,
+    /// returning the entities spawned for its colliders, action and decals
+    pub fn build(&self, from: Vec2, to: Vec2, batches: &mut Batches, commands: &mut Commands) -> Vec<Entity> {
+        let length = from.distance(to);
+        let direction = from - to;
+        let norm = Vec2::new(-direction.y, direction.x);
+        // Texture coordinates fall linearly along the wall and up it, as `origin - distance * scale`
+        let (u_origin, u_scale, v_origin, v_scale) = match self.autotile {
+            Some(Autotile::Size) => (length, 1., self.height, 1.),
+            Some(Autotile::Length) => (length / 2., 0.5, self.tiles_tall, self.tiles_tall / self.height),
+            Some(Autotile::Density(size)) => (
+                -from.dot(to - from) / length / size.x,
+                1. / size.x,
+                -self.h_off / size.y,
+                1. / size.y,
+            ),
+            None => (self.tiles_wide, self.tiles_wide / length, self.tiles_tall, self.tiles_tall / self.height),
+        };
+        let mirror = if self.mirror { -1. } else { 1. };
+        let color = self.color.unwrap_or_else(|| {
+            let shade = (direction.angle_between(Vec2::Y) / (std::f32::consts::PI * 2.)).abs();
+            match self.texture {
+                Some(_) => Color::WHITE * (0.6 + 0.2 * shade),
+                None => Color::rgb(0.4 + 0.2 * shade, 0.4 + 0.2 * shade, 0.4 + 0.2 * shade),
+            }
+        });
+
+        // Split the wall into full height segments between its openings, and lintels above them
+        let mut openings = self.openings.clone();
+        openings.sort_by(|a, b| a.start.total_cmp(&b.start));
+        let mut segments = vec![];
+        let mut lintels = vec![];
+        let mut solid_from = 0.;
+        for opening in openings {
+            let start = opening.start.clamp(solid_from, length);
+            let end = (opening.start + opening.width).clamp(start, length);
+            segments.push((solid_from, start));
+            if opening.height < self.height {
+                lintels.push((start, end, opening.height));
+            }
+            solid_from = end;
+        }
+        segments.push((solid_from, length));
+        segments.retain(|(start, end)| end - start > f32::EPSILON);
+
+        // Positions on the wall are given by their distance along it and their height, offset to
+        // either face of a thick wall
+        let side = norm.normalize() * self.thickness / 2.;
+        let point = |along: f32, offset: Vec2, up: f32| {
+            let pos = from.lerp(to, along / length) + offset;
+            Vec3::new(pos.x, up + self.h_off, pos.y)
+        };
+        // Texture coordinates run on across the segments as if the wall had no openings
+        let u = |along: f32| mirror * (u_origin - along * u_scale);
+        let v = |up: f32| v_origin - up * v_scale;
+        let top = self.height;
+
+        let mut quads = Geometry::default();
+        let faces = segments
+            .iter()
+            .map(|&(start, end)| (start, end, 0.))
+            .chain(lintels.iter().copied());
+        for (start, end, bottom) in faces {
+            quads.push(
+                [point(start, side, bottom), point(end, side, bottom), point(start, side, top), point(end, side, top)],
+                [[u(start), v(bottom)], [u(end), v(bottom)], [u(start), v(top)], [u(end), v(top)]],
+            );
+            if self.thickness > 0. {
+                // The back face runs the other way along the wall, so its texture isn't mirrored
+                let u = |along: f32| mirror * (along * u_scale - u_origin);
+                quads.push(
+                    [point(end, -side, bottom), point(start, -side, bottom), point(end, -side, top), point(start, -side, top)],
+                    [[u(end), v(bottom)], [u(start), v(bottom)], [u(end), v(top)], [u(start), v(top)]],
+                );
+            }
+        }
+
+        if self.thickness > 0. {
+            let cap_u = u_scale * self.thickness;
+            let cap_v = v_scale * self.thickness;
+            for &(start, end) in segments.iter() {
+                for (along, offset) in [(start, -side), (end, side)] {
+                    quads.push(
+                        [point(along, offset, 0.), point(along, -offset, 0.), point(along, offset, top), point(along, -offset, top)],
+                        [[0., v(0.)], [cap_u, v(0.)], [0., v(top)], [cap_u, v(top)]],
+                    );
+                }
+            }
+            for &(start, end, bottom) in lintels.iter() {
+                quads.push(
+                    [point(start, -side, bottom), point(end, -side, bottom), point(start, side, bottom), point(end, side, bottom)],
+                    [[u(start), 0.], [u(end), 0.], [u(start), cap_v], [u(end), cap_v]],
+                );
+            }
+            quads.push(
+                [point(0., side, top), point(length, side, top), point(0., -side, top), point(length, -side, top)],
+                [[u(0.), 0.], [u(length), 0.], [u(0.), cap_v], [u(length), cap_v]],
+            );
+            if self.h_off > 0. {
+                quads.push(
+                    [point(0., -side, 0.), point(length, -side, 0.), point(0., side, 0.), point(length, side, 0.)],
+                    [[u(0.), 0.], [u(length), 0.], [u(0.), cap_v], [u(length), cap_v]],
+                );
+            }
+        }
+
+        // Thick walls are closed, so only their inside is hidden
+        let cull = if self.thickness > 0. { Some(Face::Back) } else { self.cull };
+        batches.add(MaterialKey::new(color, &self.texture, cull, self.transparent), quads);
+
+        // Each full height segment gets its own collider on every face, leaving the openings
+        // passable
+        let mut spawned = vec![];
+        if self.collision {
+            let faces = match self.thickness > 0. {
+                true => vec![side, -side],
+                false => vec![Vec2::ZERO],
+            };
+            for (start, end) in segments {
+                for offset in faces.iter() {
+                    let (from, to) = (from.lerp(to, start / length) + *offset, from.lerp(to, end / length) + *offset);
+                    spawned.push(commands.spawn().insert(LineCollider::new(from, to)).id());
+                }
+                // Thick walls are closed across their ends, so they can't be walked into there
+                if self.thickness > 0. {
+                    for along in [start, end] {
+                        let end = from.lerp(to, along / length);
+                        spawned.push(commands.spawn().insert(LineCollider::new(end - side, end + side)).id());
+                    }
+                }
+            }
+        }
+        if let Some(action) = self.action.clone() {
+            spawned.push(commands.spawn().insert(Interactable {
+                action,
+                point: (from + to) / 2.,
+                radius: self.radius,
+            }).id());
+        }
+
+        for decal in self.decals.iter() {
+            spawned.extend(self.build_decal(decal, from, to, batches, commands));
+        }
+
+        spawned
+    }
+
+    /// Add a decal to the scene in front of one of this wall's faces, given the resolved positions
+    /// of the wall's end points, returning the entities spawned for its action
+    fn build_decal(
         &self,
-        commands: &'a mut Commands<'w, 's>,
-        meshes: &mut ResMut<Assets<Mesh>>,
-        materials: &mut ResMut<Assets<StandardMaterial>>,
-    ) -> EntityCommands<'w, 's, 'a> {
-        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
-        let verts = vec![
-            [self.from.x, self.h_off, self.from.y],               //bl
-            [self.to.x, self.h_off, self.to.y],                   //br
-            [self.from.x, self.height + self.h_off, self.from.y], //tl
-            [self.to.x, self.height + self.h_off, self.to.y],     //tr
+        decal: &DecalBuilder,
+        from: Vec2,
+        to: Vec2,
+        batches: &mut Batches,
+        commands: &mut Commands,
+    ) -> Vec<Entity> {
+        let length = from.distance(to);
+        let direction = from - to;
+        let norm = Vec2::new(-direction.y, direction.x);
+        // Decals default to the face that isn't culled, and run along their face so that it's
+        // their front
+        let depth = norm.normalize() * (self.thickness / 2. + DECAL_DEPTH);
+        let side = decal.side.unwrap_or(match self.cull {
+            Some(Face::Front) if self.thickness == 0. => WallSide::Back,
+            _ => WallSide::Front,
+        });
+        let start = from.lerp(to, decal.start / length);
+        let end = from.lerp(to, (decal.start + decal.width) / length);
+        let (from, to) = match side {
+            WallSide::Front => (start + depth, end + depth),
+            WallSide::Back => (end - depth, start - depth),
+        };
+        decal.wall(from, to, self.h_off).build(from, to, batches, commands)
+    }
+}
+
+/// Distance of decals and exhibits in front of the face they're placed on
+const DECAL_DEPTH: f32 = 0.01;
+
+/// Face of a wall, the front is to the right of the wall's direction
+#[derive(Clone, Copy)]
+pub enum WallSide {
+    Front,
+    Back,
+}
+
+/// A textured quad placed just in front of a face of a [WallBuilder], such as a painting or sign
+#[derive(Clone)]
+pub struct DecalBuilder {
+    /// Face of the wall the decal is placed on, the face that isn't culled if not set
+    side: Option<WallSide>,
+    /// Distance along the wall from its first end point to the start of the decal
+    start: f32,
+    /// Length of the decal along the wall
+    width: f32,
+    /// Height of the decal
+    height: f32,
+    /// Height of the bottom of the decal above the bottom of the wall
+    h_off: f32,
+    /// Texture of the decal
+    texture: Option<Handle<Image>>,
+    /// How many times to repeat the applied texture in the X and Y coordinates
+    tiles: (f32, f32),
+    /// Wether or not to flip the texture horizontally
+    mirror: bool,
+    /// Wether or not to enable transparency
+    transparent: bool,
+    /// Action taken when the decal is interacted
+    action: Option<InteractableAction>,
+}
+
+impl DecalBuilder {
+    /// Create a new untextured decal at the bottom of the wall, beginning `start` units along the
+    /// wall from its first end point
+    pub fn new(start: f32, width: f32, height: f32) -> Self {
+        Self {
+            side: None,
+            start,
+            width,
+            height,
+            h_off: 0.,
+            texture: None,
+            tiles: (1., 1.),
+            mirror: false,
+            transparent: false,
+            action: None,
+        }
+    }
+
+    /// Place the decal on the given face of the wall, even if that face is culled
+    pub fn with_side(mut self, side: WallSide) -> Self {
+        self.side = Some(side);
+        self
+    }
+
+    /// Add a height offset from the bottom of the wall
+    pub fn with_offset(mut self, off: f32) -> Self {
+        self.h_off = off;
+        self
+    }
+
+    /// Add a texture to this decal
+    pub fn with_texture(mut self, texture: Handle<Image>) -> Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// Set the amount of times to repeat the applied texture
+    pub fn with_tiles(mut self, wide: f32, tall: f32) -> Self {
+        self.tiles = (wide, tall);
+        self
+    }
+
+    /// Flip the texture horizontally
+    pub fn with_mirror(mut self, mirror: bool) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// Enable or disable transparency for this decal's texture
+    pub fn with_transparency(mut self, transparency: bool) -> Self {
+        self.transparent = transparency;
+        self
+    }
+
+    /// Add an action to take when this decal is interacted
+    pub fn with_action(mut self, action: InteractableAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    /// Create a wall without collision for the decal between the given positions, facing the
+    /// right of their direction
+    fn wall(&self, from: Vec2, to: Vec2, h_off: f32) -> WallBuilder {
+        let mut wall = WallBuilder::new(from, to)
+            .with_height(self.height)
+            .with_offset(h_off + self.h_off)
+            .with_tiles(self.tiles.0, self.tiles.1)
+            .with_mirror(self.mirror)
+            .with_cull(Face::Back)
+            .with_collision(false)
+            .with_transparency(self.transparent);
+        wall.texture = self.texture.clone();
+        wall.action = self.action.clone();
+        wall
+    }
+}
+
+/// A closed room with a wall along every side of a polygon, and a floor and ceiling covering it
+pub struct RoomBuilder {
+    /// Name used to report errors in the room's position, its walls are named after their side
+    name: String,
+    /// Corners of the room in order, the last corner is joined back to the first
+    polygon: Vec<Point>,
+    /// Walls along each side of the room, beginning at the corner with the same index
+    sides: Vec<WallBuilder>,
+    /// Floor covering the room, facing up
+    floor: FloorBuilder,
+    /// Ceiling covering the room at the height of its walls, facing down
+    ceiling: FloorBuilder,
+}
+
+impl RoomBuilder {
+    /// Create a new room with untextured walls of [WALL_HEIGHT] along every side of the polygon
+    pub fn new<P: Into<Point>>(polygon: impl IntoIterator<Item = P>) -> Self {
+        let polygon = polygon.into_iter().map(Into::into).collect::<Vec<Point>>();
+        let sides = polygon
+            .iter()
+            .zip(polygon.iter().cycle().skip(1))
+            .map(|(from, to)| WallBuilder::new(from.clone(), to.clone()).autotile_len())
+            .collect();
+
+        Self {
+            name: String::new(),
+            floor: FloorBuilder::polygon(polygon.clone()).with_cull(Face::Back),
+            ceiling: FloorBuilder::polygon(polygon.clone())
+                .with_offset(WALL_HEIGHT)
+                .with_cull(Face::Front),
+            polygon,
+            sides,
+        }
+    }
+
+    /// Set the name used to report errors in this room's position
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the height of the room's walls and ceiling
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.ceiling.height = height;
+        self.sides = self.sides
+            .into_iter()
+            .map(|side| side.with_height(height))
+            .collect();
+        self
+    }
+
+    /// Repeat the textures of the room's walls once every `width` metres across and `height` metres
+    /// up, see [WallBuilder::with_texture_size]. The floor and ceiling repeat once every `width`
+    /// metres along both axes, as `height` is only meaningful on walls
+    pub fn with_texture_size(mut self, width: f32, height: f32) -> Self {
+        self.floor = self.floor.with_texture_size(width, width);
+        self.ceiling = self.ceiling.with_texture_size(width, width);
+        self.sides = self.sides
+            .into_iter()
+            .map(|side| side.with_texture_size(width, height))
+            .collect();
+        self
+    }
+
+    /// Add a texture to every wall of the room
+    pub fn with_wall_texture(mut self, texture: Handle<Image>) -> Self {
+        self.sides = self.sides
+            .into_iter()
+            .map(|side| side.with_texture(texture.clone()))
+            .collect();
+        self
+    }
+
+    /// Add a texture to the wall beginning at the given corner, replacing the room's wall texture
+    ///
+    /// # Panics
+    /// If the room has no corner with the given index
+    pub fn with_side_texture(mut self, side: usize, texture: Handle<Image>) -> Self {
+        self.sides[side].texture = Some(texture);
+        self
+    }
+
+    /// Cut a doorway out of the wall beginning at the given corner, see [WallBuilder::with_opening]
+    ///
+    /// # Panics
+    /// If the room has no corner with the given index
+    pub fn with_side_opening(mut self, side: usize, start: f32, width: f32, height: f32) -> Self {
+        self.sides[side].openings.push(Opening { start, width, height });
+        self
+    }
+
+    /// Add a texture to the floor
+    pub fn with_floor_texture(mut self, texture: Handle<Image>) -> Self {
+        self.floor.texture = Some(texture);
+        self
+    }
+
+    /// Add a texture to the ceiling
+    pub fn with_ceiling_texture(mut self, texture: Handle<Image>) -> Self {
+        self.ceiling.texture = Some(texture);
+        self
+    }
+
+    /// Get the number of sides of this room
+    pub fn sides(&self) -> usize {
+        self.sides.len()
+    }
+
+    /// Face of the walls that points out of the room, given the resolved positions of its corners
+    fn outward_face(&self, polygon: &[Vec2]) -> Result<Face, PlanError> {
+        // Walls face to the right of their direction, which is outside a counter-clockwise room
+        match signed_area(polygon) {
+            area if area.abs() <= f32::EPSILON => Err(PlanError::NoArea(self.name.clone())),
+            area if area > 0. => Ok(Face::Front),
+            _ => Ok(Face::Back),
+        }
+    }
+
+    /// Cull the outward face of every wall, so the walls can only be seen from inside the room
+    fn face_inwards(&mut self, polygon: &[Vec2]) -> Result<(), PlanError> {
+        let cull = self.outward_face(polygon)?;
+        for side in self.sides.iter_mut() {
+            side.cull = Some(cull);
+        }
+        Ok(())
+    }
+
+    /// Add the walls, floor and ceiling of the room to the scene, returning the entities spawned
+    /// for the walls' colliders
+    pub fn build(&self, polygon: &[Vec2], batches: &mut Batches, commands: &mut Commands) -> Vec<Entity> {
+        self.floor.build(polygon, &[], batches);
+        self.ceiling.build(polygon, &[], batches);
+        self.sides
+            .iter()
+            .zip(polygon.iter().zip(polygon.iter().cycle().skip(1)))
+            .flat_map(|(side, (from, to))| side.build(*from, *to, batches, commands))
+            .collect()
+    }
+}
+
+/// Face of a [BoxBuilder], named after the axis it faces along
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BoxFace {
+    Top,
+    Bottom,
+    PosX,
+    NegX,
+    PosZ,
+    NegZ,
+}
+
+/// A closed box standing on the floor, such as a pedestal or display case
+pub struct BoxBuilder {
+    /// Name used to report errors in the box's position
+    name: String,
+    /// Centre of the bottom of the box on the floor plan
+    position: Point,
+    /// Width along X, height, and depth along Z of the box
+    size: Vec3,
+    /// Height offset of the bottom of the box
+    h_off: f32,
+    /// Textures of the faces, indexed by [BoxFace]
+    textures: [Option<Handle<Image>>; 6],
+    /// Decals on the sides of the box
+    decals: Vec<(BoxFace, DecalBuilder)>,
+    /// If this box has collision along its sides
+    collision: bool,
+    /// Wether or not this box is a glass case, with a transparent top and an inside that can be
+    /// seen through it
+    glass: bool,
+    /// Action taken when the box is interacted
+    action: Option<InteractableAction>,
+    /// What radius for the interactable action
+    radius: f32,
+}
+
+impl BoxBuilder {
+    /// Create a new untextured box on the ground with collision
+    pub fn new(position: impl Into<Point>, size: Vec3) -> Self {
+        Self {
+            name: String::new(),
+            position: position.into(),
+            size,
+            h_off: 0.,
+            textures: Default::default(),
+            decals: vec![],
+            collision: true,
+            glass: false,
+            action: None,
+            radius: 2.5,
+        }
+    }
+
+    /// Create a new glass case, whose top is transparent and whose sides and bottom can be seen
+    /// from inside. The top is never textured
+    pub fn glass_case(position: impl Into<Point>, size: Vec3) -> Self {
+        Self {
+            glass: true,
+            ..Self::new(position, size)
+        }
+    }
+
+    /// Set the name used to report errors in this box's position
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Add a height offset from the ground, which also gives the box a bottom face
+    pub fn with_offset(mut self, off: f32) -> Self {
+        self.h_off = off;
+        self
+    }
+
+    /// Add a texture to every face of this box
+    pub fn with_texture(mut self, texture: Handle<Image>) -> Self {
+        self.textures = [(); 6].map(|_| Some(texture.clone()));
+        self
+    }
+
+    /// Add a texture to one face of this box, replacing the box's texture. The top is textured
+    /// upright for someone standing on the [BoxFace::NegZ] side
+    pub fn with_face_texture(mut self, face: BoxFace, texture: Handle<Image>) -> Self {
+        self.textures[face as usize] = Some(texture);
+        self
+    }
+
+    /// Place a decal on a side of this box, measured from the side's right end when facing it
+    ///
+    /// # Panics
+    /// If the face is the top or bottom of the box
+    pub fn with_decal(mut self, face: BoxFace, decal: DecalBuilder) -> Self {
+        assert!(!matches!(face, BoxFace::Top | BoxFace::Bottom), "decals can only be placed on the sides of boxes");
+        self.decals.push((face, decal));
+        self
+    }
+
+    /// Set the collision for this box
+    pub fn with_collision(mut self, collision: bool) -> Self {
+        self.collision = collision;
+        self
+    }
+
+    /// Add an action to take when this box is interacted, such as viewing the image on its top
+    pub fn with_action(mut self, action: InteractableAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    /// Set the interaction radius of this box
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Add the box's geometry to the scene around the resolved position of the centre of its
+    /// bottom, returning the entities spawned for its colliders, decals and action
+    pub fn build(&self, position: Vec2, batches: &mut Batches, commands: &mut Commands) -> Vec<Entity> {
+        let half = Vec2::new(self.size.x, self.size.z) / 2.;
+        let (min, max) = (position - half, position + half);
+        let top = self.h_off + self.size.y;
+
+        // Each side is a wall facing outwards, with a collider along it
+        let mut spawned = vec![];
+        let sides = [
+            (BoxFace::PosX, Vec2::new(max.x, min.y), max),
+            (BoxFace::NegX, Vec2::new(min.x, max.y), min),
+            (BoxFace::PosZ, max, Vec2::new(min.x, max.y)),
+            (BoxFace::NegZ, min, Vec2::new(max.x, min.y)),
         ];
+        for (face, from, to) in sides {
+            let mut side = WallBuilder::new(from, to)
+                .with_height(self.size.y)
+                .with_offset(self.h_off)
+                .with_collision(self.collision);
+            // The sides of a glass case are seen from both sides
+            if !self.glass {
+                side = side.with_cull(Face::Back);
+            }
+            side.texture = self.textures[face as usize].clone();
+            side.decals = self.decals
+                .iter()
+                .filter(|(decal_face, _)| *decal_face == face)
+                .map(|(_, decal)| decal.clone().with_side(WallSide::Front))
+                .collect();
+            spawned.extend(side.build(from, to, batches, commands));
+        }
 
-        let direction = self.from - self.to;
-        let norm = Vec2::new(-direction.y, direction.x);
+        // The lid is built from its corner with the highest X and Z, which faces it up and turns
+        // its texture upright from the NegZ side
+        let rect = |from: Vec2, to: Vec2| [from, Vec2::new(to.x, from.y), to, Vec2::new(from.x, to.y)];
+        let mut lid = FloorBuilder::new(max, min)
+            .with_offset(top)
+            .with_brightness(1.)
+            .with_cull(Face::Back);
+        match self.glass {
+            true => lid = lid.with_color(Color::rgba(0.8, 0.9, 1., 0.2)).with_transparency(true),
+            false => lid.texture = self.textures[BoxFace::Top as usize].clone(),
+        }
+        lid.build(&rect(max, min), &[], batches);
 
-        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[norm.x, 0., norm.y]; 4]);
-        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, verts);
-        mesh.insert_attribute(
-            Mesh::ATTRIBUTE_UV_0,
-            vec![
-                [self.tiles_wide, self.tiles_tall],
-                [0., self.tiles_tall],
-                [self.tiles_wide, 0.],
-                [0., 0.],
-            ],
+        // The bottom is hidden on the ground, unless it's seen from inside a glass case
+        if self.h_off > 0. || self.glass {
+            let from = Vec2::new(max.x, min.y);
+            let to = Vec2::new(min.x, max.y);
+            let mut bottom = FloorBuilder::new(from, to)
+                .with_offset(self.h_off)
+                .with_brightness(0.6);
+            if !self.glass {
+                bottom = bottom.with_cull(Face::Back);
+            }
+            bottom.texture = self.textures[BoxFace::Bottom as usize].clone();
+            bottom.build(&rect(from, to), &[], batches);
+        }
+
+        if let Some(action) = self.action.clone() {
+            spawned.push(commands.spawn().insert(Interactable {
+                action,
+                point: position,
+                radius: self.radius,
+            }).id());
+        }
+
+        spawned
+    }
+}
+
+/// Height of the top of a pedestal that an artifact stands on
+pub const PEDESTAL_HEIGHT: f32 = 1.0;
+
+/// A glTF model standing on a pedestal, such as an artifact
+pub struct ArtifactBuilder {
+    /// Pedestal that the model stands on, which also gives the artifact its name and collider
+    pedestal: BoxBuilder,
+    /// First scene of the model
+    model: Handle<Scene>,
+    /// Scale of the model
+    scale: f32,
+    /// Rotation of the model around the vertical axis in radians
+    rotation: f32,
+    /// Action taken when the artifact is interacted
+    action: Option<InteractableAction>,
+    /// What radius for the interactable action
+    radius: f32,
+}
+
+impl ArtifactBuilder {
+    /// Create a new model standing on an untextured pedestal of [PEDESTAL_HEIGHT], centred on the
+    /// given position
+    pub fn new(position: impl Into<Point>, model: Handle<Scene>) -> Self {
+        Self {
+            pedestal: BoxBuilder::new(position, Vec3::new(0.8, PEDESTAL_HEIGHT, 0.8)),
+            model,
+            scale: 1.,
+            rotation: 0.,
+            action: None,
+            radius: 2.5,
+        }
+    }
+
+    /// Set the name used to report errors in this artifact's position
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.pedestal = self.pedestal.with_name(name);
+        self
+    }
+
+    /// Set the width, height and depth of the pedestal
+    pub fn with_pedestal_size(mut self, size: Vec3) -> Self {
+        self.pedestal.size = size;
+        self
+    }
+
+    /// Add a texture to every face of the pedestal
+    pub fn with_pedestal_texture(mut self, texture: Handle<Image>) -> Self {
+        self.pedestal = self.pedestal.with_texture(texture);
+        self
+    }
+
+    /// Set the scale of the model
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the rotation of the model around the vertical axis in radians
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Add an action to take when this artifact is interacted
+    pub fn with_action(mut self, action: InteractableAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    /// Set the interaction radius of this artifact
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Add the pedestal to the scene at the resolved position, and spawn the model on top of it once
+    /// it has loaded. Returns the entities spawned for the model, the pedestal's colliders and the
+    /// artifact's action
+    pub fn build(&self, position: Vec2, batches: &mut Batches, commands: &mut Commands) -> Vec<Entity> {
+        let mut spawned = self.pedestal.build(position, batches, commands);
+
+        let transform = Transform::from_xyz(position.x, self.pedestal.h_off + self.pedestal.size.y, position.y)
+            .with_rotation(Quat::from_rotation_y(self.rotation))
+            .with_scale(Vec3::splat(self.scale));
+        spawned.push(commands
+            .spawn_bundle(TransformBundle::from_transform(transform))
+            .with_children(|parent| {
+                parent.spawn_scene(self.model.clone());
+            })
+            .id()
         );
 
-        mesh.set_indices(Some(Indices::U16(vec![0, 2, 1, 3, 1, 2])));
-
-        let mut command = commands.spawn_bundle(PbrBundle {
-            mesh: meshes.add(mesh.clone()),
-            material: materials.add(StandardMaterial {
-                base_color: self.color,
-                base_color_texture: self.texture.clone(),
-                cull_mode: self.cull,
-                unlit: true,
-                alpha_mode: if self.transparent { AlphaMode::Blend } else { AlphaMode::Opaque },
-                ..Default::default()
-            }),
-            ..default()
+        if let Some(action) = self.action.clone() {
+            spawned.push(commands.spawn().insert(Interactable {
+                action,
+                point: position,
+                radius: self.radius,
+            }).id());
+        }
+
+        spawned
+    }
+}
+
+/// Height of the centre of an exhibit's artwork
+pub const ARTWORK_CENTRE: f32 = 2.0;
+/// Height of the bottom of an exhibit's placard and audio station
+pub const PLACARD_HEIGHT: f32 = 1.35;
+
+/// An artwork hung on a wall, with a placard beside it that shows the exhibit's tombstone and an
+/// optional audio station beside the placard
+pub struct ExhibitBuilder {
+    /// Name and end points of the wall the exhibit hangs on, the exhibit faces the right of the
+    /// wall's direction
+    host: Edge,
+    /// Distance along the host wall from its first end point to the start of the artwork
+    start: f32,
+    /// Width and height of the artwork
+    size: Vec2,
+    /// Texture of the artwork
+    image: Handle<Image>,
+    /// Wether or not to enable transparency for the artwork
+    transparent: bool,
+    /// Texture and action of the placard
+    placard: Option<(Handle<Image>, InteractableAction)>,
+    /// Texture of the audio station and the track it plays
+    audio: Option<(Handle<Image>, Handle<AudioSource>)>,
+    /// Whether reading the placard looks at the artwork close up
+    close_up: bool,
+}
+
+impl ExhibitBuilder {
+    /// Create a new exhibit with no placard, beginning `start` units along the wall between `from`
+    /// and `to` and centred at [ARTWORK_CENTRE]
+    pub fn new(from: impl Into<Point>, to: impl Into<Point>, start: f32, size: Vec2, image: Handle<Image>) -> Self {
+        Self {
+            host: Edge {
+                name: String::new(),
+                from: from.into(),
+                to: to.into(),
+            },
+            start,
+            size,
+            image,
+            transparent: false,
+            placard: None,
+            audio: None,
+            close_up: false,
+        }
+    }
+
+    /// Set the name used to report errors in this exhibit's position
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.host.name = name.into();
+        self
+    }
+
+    /// Enable or disable transparency for the artwork
+    pub fn with_transparency(mut self, transparency: bool) -> Self {
+        self.transparent = transparency;
+        self
+    }
+
+    /// Add a placard after the artwork that takes the given action when interacted
+    pub fn with_placard(mut self, texture: Handle<Image>, action: InteractableAction) -> Self {
+        self.placard = Some((texture, action));
+        self
+    }
+
+    /// Add an audio station after the placard that plays the given track when interacted
+    pub fn with_audio(mut self, texture: Handle<Image>, source: Handle<AudioSource>) -> Self {
+        self.audio = Some((texture, source));
+        self
+    }
+
+    /// Look at the artwork close up while reading a placard that shows a tombstone, instead of
+    /// showing the tombstone over a backdrop
+    pub fn with_close_up(mut self) -> Self {
+        self.close_up = true;
+        self
+    }
+
+    /// Add the exhibit's artwork, placard and audio station to the scene as decals on its host wall,
+    /// given the resolved positions of the exhibit's end points and the host with its own end
+    /// points, returning the entities spawned for their actions. Without a host, they are placed
+    /// in front of a thin wall between the exhibit's end points
+    pub fn build(
+        &self,
+        from: Vec2,
+        to: Vec2,
+        host: Option<(&WallBuilder, (Vec2, Vec2))>,
+        batches: &mut Batches,
+        commands: &mut Commands,
+    ) -> Vec<Entity> {
+        let bare;
+        let (host, (host_from, host_to)) = match host {
+            Some(host) => host,
+            None => {
+                bare = WallBuilder::new(from, to);
+                (&bare, (from, to))
+            },
+        };
+        let direction = (to - from).normalize();
+        let length = from.distance(to);
+        // The exhibit faces the right of its direction, which is the host's back if it runs the
+        // other way
+        let reversed = host_from.distance(to) < host_from.distance(from);
+        let decal = |start: f32, width: f32, height: f32| match reversed {
+            false => DecalBuilder::new(start, width, height).with_side(WallSide::Front),
+            true => DecalBuilder::new(length - start - width, width, height).with_side(WallSide::Back),
+        };
+
+        let mut decals = vec![
+            decal(self.start, self.size.x, self.size.y)
+                .with_texture(self.image.clone())
+                .with_offset((ARTWORK_CENTRE - self.size.y / 2.).max(0.))
+                .with_transparency(self.transparent),
+        ];
+
+        let mut end = self.start + self.size.x;
+        if let Some((texture, action)) = &self.placard {
+            let mut action = action.clone();
+            if let InteractableAction::Tombstone { view: view @ None, .. } = &mut action {
+                if self.close_up {
+                    let centre = from + direction * (self.start + self.size.x / 2.);
+                    let centre = Vec3::new(centre.x, ARTWORK_CENTRE.max(self.size.y / 2.), centre.y);
+                    // Far enough back from the host's face for the artwork to fill most of the view
+                    let distance = (self.size.y * 0.9).max(self.size.x * 0.5).max(0.5) + host.thickness / 2.;
+                    let normal = Vec3::new(direction.y, 0., -direction.x);
+                    *view = Some(ViewPose {
+                        eye: centre + normal * distance,
+                        target: centre + normal * host.thickness / 2.,
+                    });
+                }
+            }
+            decals.push(decal(end + 0.3, 0.5, 0.25)
+                .with_texture(texture.clone())
+                .with_offset(PLACARD_HEIGHT)
+                .with_action(action));
+            end += 0.8;
+        }
+        if let Some((texture, source)) = &self.audio {
+            decals.push(decal(end + 0.2, 0.5, 0.5)
+                .with_texture(texture.clone())
+                .with_offset(PLACARD_HEIGHT)
+                .with_transparency(true)
+                .with_action(InteractableAction::Audio { source: source.clone() }));
+        }
+
+        decals
+            .iter()
+            .flat_map(|decal| host.build_decal(decal, host_from, host_to, batches, commands))
+            .collect()
+    }
+}
+
+/// Area enclosed by a polygon on the X/Z plane, positive if its corners are counter-clockwise
+fn signed_area(polygon: &[Vec2]) -> f32 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum::<f32>() / 2.
+}
+
+/// Split a simple polygon into triangles by clipping ears, returning the indices of each
+/// triangle's corners wound counter-clockwise on the X/Z plane. `ring` is the order that the
+/// polygon's corners are joined in
+fn triangulate(points: &[Vec2], mut ring: Vec<u32>) -> Vec<u32> {
+    let area = |ring: &[u32]| signed_area(&ring.iter().map(|&idx| points[idx as usize]).collect::<Vec<_>>());
+    if area(&ring) < 0. {
+        ring.reverse();
+    }
+
+    let mut indices = Vec::with_capacity(ring.len().saturating_sub(2) * 3);
+    while ring.len() > 3 {
+        let len = ring.len();
+        let corners = |i: usize| [ring[(i + len - 1) % len], ring[i], ring[(i + 1) % len]];
+        // An ear is a convex corner whose triangle doesn't contain any other corner
+        let ear = (0..len).find(|&i| {
+            let [a, b, c] = corners(i).map(|idx| points[idx as usize]);
+            (b - a).perp_dot(c - b) > 0. && ring
+                .iter()
+                .filter(|idx| !corners(i).contains(idx))
+                .all(|&idx| !in_triangle(points[idx as usize], a, b, c))
         });
 
-        if self.collision {
-            command
-                .insert(LineCollider {
-                    from: self.from,
-                    to: self.to,
-                    len: self.from.distance(self.to),
-                });
+        // Only self-intersecting polygons have no ears. Clipping a convex corner anyway still
+        // gives every corner a triangle, though some of them may overlap
+        let i = ear.unwrap_or_else(|| {
+            warn!("Floor outline crosses itself, some of its triangles may overlap");
+            (0..len)
+                .find(|&i| {
+                    let [a, b, c] = corners(i).map(|idx| points[idx as usize]);
+                    (b - a).perp_dot(c - b) > 0.
+                })
+                .unwrap_or(0)
+        });
+        indices.extend(corners(i));
+        ring.remove(i);
+    }
+    indices.extend(ring);
+    indices
+}
+
+/// Join a hole into a ring of corners along a pair of coincident edges, so that the ring and the
+/// hole can be triangulated as a single polygon
+fn bridge(points: &[Vec2], ring: Vec<u32>, mut hole: Vec<u32>) -> Vec<u32> {
+    let area = |ring: &[u32]| signed_area(&ring.iter().map(|&idx| points[idx as usize]).collect::<Vec<_>>());
+    // The hole has to wind the opposite way to the ring it is cut out of
+    if (area(&ring) > 0.) == (area(&hole) > 0.) {
+        hole.reverse();
+    }
+
+    let edges = |ring: &[u32]| (0..ring.len())
+        .map(|i| (points[ring[i] as usize], points[ring[(i + 1) % ring.len()] as usize]))
+        .collect::<Vec<_>>();
+    let edges = [edges(&ring), edges(&hole)].concat();
+
+    // Join the closest pair of corners that can see each other
+    let mut pairs = (0..ring.len())
+        .flat_map(|i| (0..hole.len()).map(move |j| (i, j)))
+        .collect::<Vec<_>>();
+    let distance = |&(i, j): &(usize, usize)| points[ring[i] as usize].distance_squared(points[hole[j] as usize]);
+    pairs.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+    let (i, j) = pairs
+        .into_iter()
+        .find(|&(i, j)| {
+            let (from, to) = (points[ring[i] as usize], points[hole[j] as usize]);
+            edges.iter().all(|&(a, b)| !crosses(from, to, a, b))
+        })
+        .unwrap_or((0, 0));
+
+    ring[..=i]
+        .iter()
+        .chain(hole[j..].iter())
+        .chain(hole[..=j].iter())
+        .chain(ring[i..].iter())
+        .copied()
+        .collect()
+}
+
+/// Check if two line segments cross each other, not counting segments that only touch
+fn crosses(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let side = |from: Vec2, to: Vec2, p: Vec2| (to - from).perp_dot(p - from);
+    side(a, b, c) * side(a, b, d) < 0. && side(c, d, a) * side(c, d, b) < 0.
+}
+
+/// Check if a point is inside or on the edge of a counter-clockwise triangle
+fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    (b - a).perp_dot(p - a) >= 0. && (c - b).perp_dot(p - b) >= 0. && (a - c).perp_dot(p - c) >= 0.
+}
+
+/// Triangles of static geometry, merged with other geometry that shares its material
+#[derive(Default)]
+struct Geometry {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    indices: Vec<u32>,
+}
+
+impl Geometry {
+    /// Add a quad with corners ordered bottom start, bottom end, top start, top end. Its front face
+    /// is the side that bottom start, top start and bottom end appear counter-clockwise from
+    fn push(&mut self, corners: [Vec3; 4], uvs: [[f32; 2]; 4]) {
+        let idx = self.positions.len() as u32;
+        let normal = (corners[2] - corners[0]).cross(corners[1] - corners[0]).normalize_or_zero();
+        self.positions.extend(corners.map(|corner| corner.to_array()));
+        self.normals.extend([normal.to_array(); 4]);
+        self.uvs.extend(uvs);
+        self.indices.extend([0, 2, 1, 3, 1, 2].map(|i| idx + i));
+    }
+
+    /// Add all triangles of another piece of geometry to this one
+    fn append(&mut self, other: Geometry) {
+        let idx = self.positions.len() as u32;
+        self.positions.extend(other.positions);
+        self.normals.extend(other.normals);
+        self.uvs.extend(other.uvs);
+        self.indices.extend(other.indices.into_iter().map(|i| idx + i));
+    }
+
+    /// Move the geometry so that it is centred on the origin, returning where its centre was
+    fn centre(&mut self) -> Vec3 {
+        let centre = self.positions
+            .iter()
+            .fold(Vec3::ZERO, |sum, &position| sum + Vec3::from(position))
+            / self.positions.len().max(1) as f32;
+        for position in self.positions.iter_mut() {
+            *position = (Vec3::from(*position) - centre).to_array();
         }
-        if let Some(action) = self.action.clone() {
-            command
-                .insert(Interactable {
-                    action,
-                    point: (self.from + self.to) / 2.,
-                    radius: self.radius,
-                });
+        centre
+    }
+
+    fn into_mesh(self) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs);
+        mesh.set_indices(Some(Indices::U32(self.indices)));
+        mesh
+    }
+}
+
+/// Parameters of the unlit [StandardMaterial] used by walls and floors, which can be shared by
+/// every element that has the same ones
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct MaterialKey {
+    /// Bits of the material's RGBA color
+    color: [u32; 4],
+    texture: Option<Handle<Image>>,
+    cull: Option<Face>,
+    transparent: bool,
+}
+
+impl MaterialKey {
+    fn new(color: Color, texture: &Option<Handle<Image>>, cull: Option<Face>, transparent: bool) -> Self {
+        Self {
+            color: color.as_rgba_f32().map(f32::to_bits),
+            texture: texture.clone(),
+            cull,
+            transparent,
         }
+    }
 
-        command
+    /// Material shown until the texture has loaded, which is the material without its texture
+    fn placeholder(&self) -> StandardMaterial {
+        let [r, g, b, a] = self.color.map(f32::from_bits);
+        StandardMaterial {
+            base_color: Color::rgba(r, g, b, a),
+            base_color_texture: None,
+            cull_mode: self.cull,
+            unlit: true,
+            alpha_mode: if self.transparent { AlphaMode::Blend } else { AlphaMode::Opaque },
+            ..Default::default()
+        }
     }
 }
 
+/// Static geometry of a scene, grouped by material so that it can be drawn with as few meshes
+/// as possible
+#[derive(Default)]
+pub struct Batches {
+    /// Opaque geometry merged into one mesh per material
+    opaque: HashMap<MaterialKey, Geometry>,
+    /// Transparent geometry, which keeps one mesh per element placed at its centre, so that
+    /// overlapping elements are drawn back to front
+    transparent: Vec<(MaterialKey, Geometry)>,
+}
+
+impl Batches {
+    fn add(&mut self, key: MaterialKey, geometry: Geometry) {
+        match key.transparent {
+            true => self.transparent.push((key, geometry)),
+            false => self.opaque.entry(key).or_default().append(geometry),
+        }
+    }
+
+    /// Spawn a mesh for every batch with a placeholder material, returning the spawned entities.
+    /// Transparent meshes are moved to their centre, as that is what they are sorted by
+    fn spawn(
+        self,
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<StandardMaterial>>,
+    ) -> Vec<Entity> {
+        let mut handles = HashMap::<MaterialKey, Handle<StandardMaterial>>::default();
+        let transparent = self.transparent.into_iter().map(|(key, mut geometry)| {
+            let centre = geometry.centre();
+            (key, geometry, centre)
+        });
+        self.opaque
+            .into_iter()
+            .map(|(key, geometry)| (key, geometry, Vec3::ZERO))
+            .chain(transparent)
+            .map(|(key, geometry, centre)| {
+                let texture = key.texture.clone();
+                let material = handles
+                    .entry(key)
+                    .or_insert_with_key(|key| materials.add(key.placeholder()))
+                    .clone();
+                let mut command = commands.spawn_bundle(PbrBundle {
+                    mesh: meshes.add(geometry.into_mesh()),
+                    material,
+                    transform: Transform::from_translation(centre),
+                    ..default()
+                });
+                if let Some(texture) = texture {
+                    command.insert(PendingTexture(texture));
+                }
+                command.id()
+            })
+            .collect()
+    }
+}
+
+/// Texture of an entity's material that is still loading, which is applied to the material once
+/// it is ready
+#[derive(Component)]
+pub struct PendingTexture(Handle<Image>);
+
+/// Give materials their textures once the textures have loaded and been configured, or the
+/// checkerboard placeholder if they failed to load. This runs after the layout is built in the
+/// same frame, so textures that are already loaded never show their placeholder
+pub fn apply_textures(
+    mut commands: Commands,
+    pending: Query<(Entity, &Handle<StandardMaterial>, &PendingTexture)>,
+    images: Res<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    resources: Res<GlobalResources>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let sampler = texture_sampler();
+    for (entity, material, PendingTexture(texture)) in pending.iter() {
+        let texture = match asset_server.get_load_state(texture) {
+            LoadState::Failed => resources.placeholder.clone(),
+            _ if images.get(texture).is_some_and(|image| image.sampler_descriptor == sampler) => texture.clone(),
+            _ => continue,
+        };
+        if let Some(material) = materials.get_mut(material) {
+            material.base_color_texture = Some(texture);
+        }
+        commands.entity(entity).remove::<PendingTexture>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Triangulate an outline with holes the way [FloorBuilder::build] does, returning the
+    /// corners and the indices of the triangles
+    fn fill(outline: &[(f32, f32)], holes: &[&[(f32, f32)]]) -> (Vec<Vec2>, Vec<u32>) {
+        let points = outline
+            .iter()
+            .chain(holes.iter().copied().flatten())
+            .map(|&point| Vec2::from(point))
+            .collect::<Vec<_>>();
+        let mut ring = (0..outline.len() as u32).collect();
+        let mut start = outline.len() as u32;
+        for hole in holes {
+            ring = bridge(&points, ring, (start..start + hole.len() as u32).collect());
+            start += hole.len() as u32;
+        }
+        let indices = triangulate(&points, ring);
+        (points, indices)
+    }
+
+    /// Total area of the triangles, checking that each one is counter-clockwise
+    fn area(points: &[Vec2], indices: &[u32]) -> f32 {
+        indices
+            .chunks(3)
+            .map(|triangle| {
+                let area = signed_area(&triangle.iter().map(|&idx| points[idx as usize]).collect::<Vec<_>>());
+                assert!(area >= 0., "triangle {:?} is clockwise", triangle);
+                area
+            })
+            .sum()
+    }
+
+    #[test]
+    fn convex_polygon() {
+        let (points, indices) = fill(&[(0., 0.), (2., 0.), (3., 1.), (2., 2.), (0., 2.)], &[]);
+        assert_eq!(indices.len(), 3 * 3);
+        assert!((area(&points, &indices) - 5.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn clockwise_outline_is_filled() {
+        let (points, indices) = fill(&[(0., 0.), (0., 2.), (2., 2.), (2., 0.)], &[]);
+        assert_eq!(indices.len(), 2 * 3);
+        assert!((area(&points, &indices) - 4.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn concave_polygon() {
+        let outline = [(0., 0.), (2., 0.), (2., 1.), (1., 1.), (1., 2.), (0., 2.)];
+        let (points, indices) = fill(&outline, &[]);
+        assert_eq!(indices.len(), 4 * 3);
+        assert!((area(&points, &indices) - 3.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn one_hole() {
+        let outline = [(0., 0.), (4., 0.), (4., 4.), (0., 4.)];
+        let hole = [(1., 1.), (3., 1.), (3., 3.), (1., 3.)];
+        let (points, indices) = fill(&outline, &[&hole]);
+        // The bridge into the hole repeats one corner of each
+        assert_eq!(indices.len(), 8 * 3);
+        assert!((area(&points, &indices) - 12.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn two_holes() {
+        let outline = [(0., 0.), (6., 0.), (6., 4.), (0., 4.)];
+        let left = [(1., 1.), (2., 1.), (2., 3.), (1., 3.)];
+        let right = [(4., 3.), (5., 3.), (5., 1.), (4., 1.)];
+        let (points, indices) = fill(&outline, &[&left, &right]);
+        assert_eq!(indices.len(), 14 * 3);
+        assert!((area(&points, &indices) - 20.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn collinear_corners() {
+        let outline = [(0., 0.), (1., 0.), (2., 0.), (2., 1.), (2., 2.), (1., 2.), (0., 2.), (0., 1.)];
+        let (points, indices) = fill(&outline, &[]);
+        assert_eq!(indices.len(), 6 * 3);
+        assert!((area(&points, &indices) - 4.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn floors_with_too_few_points_are_rejected() {
+        let plan = FloorPlan::new()
+            .with_vertex("a", (0., 0.))
+            .with_vertex("b", (2., 0.))
+            .with_vertex("c", (0., 2.));
+        let outline = FloorBuilder::polygon(["a", "b"]).with_name("line");
+        assert!(matches!(outline.outline(&plan), Err(PlanError::TooFewPoints(name)) if name == "line"));
+        let hole = FloorBuilder::polygon(["a", "b", "c"]).with_hole(["a"]).with_name("pinhole");
+        assert!(matches!(hole.outline(&plan), Err(PlanError::TooFewPoints(name)) if name == "pinhole"));
+    }
+
+    #[test]
+    fn rooms_without_area_are_rejected() {
+        let polygon = [Vec2::new(0., 0.), Vec2::new(1., 0.), Vec2::new(2., 0.)];
+        let mut room = RoomBuilder::new(polygon).with_name("corridor");
+        assert!(matches!(room.face_inwards(&polygon), Err(PlanError::NoArea(name)) if name == "corridor"));
+    }
+
+    #[test]
+    fn self_intersecting_outline_is_fully_triangulated() {
+        // Every convex corner of this outline has another corner in its triangle
+        let (_, indices) = fill(&[(2., 2.), (1., 0.), (3., 4.), (2., 1.), (2., 0.)], &[]);
+        assert_eq!(indices.len(), 3 * 3);
+    }
+}
@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// File that settings are read from and saved to on native builds, relative to the working
+/// directory. It isn't tracked, `settings.example.toml` documents every setting
+#[cfg(not(target_arch = "wasm32"))]
+const SETTINGS_PATH: &str = "settings.toml";
+/// Local storage key that settings are read from on the web
+#[cfg(target_arch = "wasm32")]
+const SETTINGS_KEY: &str = "museum-settings";
+/// Seconds between each check for changed settings
+const RELOAD_INTERVAL: f32 = 1.;
+/// Smallest and largest vertical field of view in degrees
+const FOV_RANGE: (f32, f32) = (40., 120.);
+/// Smallest and largest multiple of the default mouse sensitivity
+const SENSITIVITY_RANGE: (f32, f32) = (0.1, 5.);
+/// Smallest and largest walking speed in metres per second
+const WALK_SPEED_RANGE: (f32, f32) = (1., 20.);
+/// Smallest and largest share of the camera's last position kept each frame. Anything much closer
+/// to 1 makes the camera lag far behind the mouse
+const SMOOTHING_RANGE: (f32, f32) = (0., 0.9);
+
+/// Controls and camera settings, read as TOML from [SETTINGS_PATH] on native builds or the
+/// [SETTINGS_KEY] local storage item on the web. Missing fields keep their default, and changes
+/// are picked up while the museum is running
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct Settings {
+    /// Move the camera down when the mouse moves up
+    pub invert_y: bool,
+    /// Vertical field of view in degrees
+    pub fov: f32,
+    /// Multiple of the default mouse sensitivity
+    pub sensitivity: f32,
+    /// Walking speed in metres per second
+    pub walk_speed: f32,
+    /// Bob the camera up and down while walking
+    pub head_bob: bool,
+    /// How much of the camera's last position is kept each frame, from 0 for no smoothing up to
+    /// 0.9
+    pub smoothing: f32,
+    /// Kept last, as TOML tables have to come after plain values
+    pub keys: KeyBindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            invert_y: false,
+            fov: 70.,
+            sensitivity: 1.,
+            walk_speed: 10.,
+            head_bob: true,
+            smoothing: 0.7,
+            keys: KeyBindings::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Keep every number in the range the menu allows, replacing any that isn't a number with
+    /// its default. Settings are sanitized whenever they are read or edited
+    pub fn sanitized(self) -> Self {
+        let default = Self::default();
+        let clamp = |value: f32, (min, max): (f32, f32), default: f32| match value.is_nan() {
+            true => default,
+            false => value.clamp(min, max),
+        };
+        Self {
+            fov: clamp(self.fov, FOV_RANGE, default.fov),
+            sensitivity: clamp(self.sensitivity, SENSITIVITY_RANGE, default.sensitivity),
+            walk_speed: clamp(self.walk_speed, WALK_SPEED_RANGE, default.walk_speed),
+            smoothing: clamp(self.smoothing, SMOOTHING_RANGE, default.smoothing),
+            ..self
+        }
+    }
+
+    /// Smoother used by the camera while walking
+    pub fn smoother(&self) -> Smoother {
+        Smoother::new(self.smoothing)
+    }
+}
+
+/// Keys used to walk and interact, named as in [KeyCode], such as `"W"` or `"Up"`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub forward: KeyCode,
+    pub back: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    /// Interact with exhibits, and leave the text, image or model being looked at
+    pub interact: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            forward: KeyCode::W,
+            back: KeyCode::S,
+            left: KeyCode::A,
+            right: KeyCode::D,
+            interact: KeyCode::E,
+        }
+    }
+}
+
+/// Name of a key as it is shown in prompts, such as `[e]`
+pub fn key_name(key: KeyCode) -> String {
+    format!("{:?}", key).to_lowercase()
+}
+
+/// Last settings text that was read, and when to check for changes again
+pub struct SettingsSource {
+    text: Option<String>,
+    timer: Timer,
+}
+
+/// Read the settings text from the settings file
+#[cfg(not(target_arch = "wasm32"))]
+fn read_source() -> Option<String> {
+    std::fs::read_to_string(SETTINGS_PATH).ok()
+}
+
+/// Read the settings text from local storage
+#[cfg(target_arch = "wasm32")]
+fn read_source() -> Option<String> {
+    web_sys::window()?
+        .local_storage()
+        .ok()??
+        .get_item(SETTINGS_KEY)
+        .ok()?
+}
+
+/// Write the settings text to the settings file, returning whether it was written
+#[cfg(not(target_arch = "wasm32"))]
+fn write_source(text: &str) -> bool {
+    std::fs::write(SETTINGS_PATH, text)
+        .map_err(|err| error!("Couldn't save settings to {}: {}", SETTINGS_PATH, err))
+        .is_ok()
+}
+
+/// Write the settings text to local storage, returning whether it was written
+#[cfg(target_arch = "wasm32")]
+fn write_source(text: &str) -> bool {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .is_some_and(|storage| storage.set_item(SETTINGS_KEY, text).is_ok())
+}
+
+/// Save settings changed in the museum, so that they are kept the next time it starts
+pub fn save(settings: &Settings, source: &mut SettingsSource) {
+    match toml::to_string(settings) {
+        // Saved settings are already applied, so they don't need to be reloaded
+        Ok(text) => if write_source(&text) {
+            source.text = Some(text);
+        },
+        Err(err) => error!("Couldn't save settings: {}", err),
+    }
+}
+
+/// Parse settings text, keeping the defaults if there is none or it is invalid
+fn parse(text: Option<&str>) -> Settings {
+    match text.map(toml::from_str::<Settings>) {
+        Some(Ok(settings)) => settings.sanitized(),
+        Some(Err(err)) => {
+            error!("Invalid settings, using the defaults: {}", err);
+            Settings::default()
+        },
+        None => Settings::default(),
+    }
+}
+
+/// Read the settings and where they came from, to be inserted as resources before startup
+pub fn load() -> (Settings, SettingsSource) {
+    let text = read_source();
+    let settings = parse(text.as_deref());
+    let source = SettingsSource {
+        text,
+        timer: Timer::from_seconds(RELOAD_INTERVAL, true),
+    };
+    (settings, source)
+}
+
+/// Read the settings again every [RELOAD_INTERVAL], replacing them if they have changed
+pub fn reload_settings(
+    time: Res<Time>,
+    mut source: ResMut<SettingsSource>,
+    mut settings: ResMut<Settings>,
+) {
+    if !source.timer.tick(time.delta()).just_finished() {
+        return
+    }
+    let text = read_source();
+    if text == source.text {
+        return
+    }
+
+    let reloaded = parse(text.as_deref());
+    source.text = text;
+    if reloaded != *settings {
+        info!("Settings changed");
+        *settings = reloaded;
+    }
+}
+
+/// Apply changed settings to the camera and prompts. Other settings are read each frame
+pub(crate) fn apply_settings(
+    settings: Res<Settings>,
+    mut players: Query<(&mut PerspectiveProjection, &mut Smoother, &Player)>,
+    mut exit_prompt: Query<&mut Text, With<ExitPrompt>>,
+) {
+    if !settings.is_changed() {
+        return
+    }
+
+    for (mut projection, mut smoother, player) in players.iter_mut() {
+        projection.fov = settings.fov.to_radians();
+        // Inspect mode moves the camera without smoothing until it is left
+        if player.inspected.is_none() {
+            *smoother = settings.smoother();
+        }
+    }
+    for mut text in exit_prompt.iter_mut() {
+        text.sections[0].value = format!("[{}] Exit", key_name(settings.keys.interact));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_range_settings_are_clamped() {
+        let settings = parse(Some("fov = 400.0\nsensitivity = -1.0\nwalk_speed = 1000.0\nsmoothing = 1.5"));
+        assert_eq!(settings.fov, FOV_RANGE.1);
+        assert_eq!(settings.sensitivity, SENSITIVITY_RANGE.0);
+        assert_eq!(settings.walk_speed, WALK_SPEED_RANGE.1);
+        assert_eq!(settings.smoothing, SMOOTHING_RANGE.1);
+    }
+
+    #[test]
+    fn settings_that_are_not_numbers_keep_their_default() {
+        let settings = parse(Some("fov = nan\nsmoothing = nan"));
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn settings_in_range_are_kept() {
+        let settings = parse(Some("fov = 90.0\nsmoothing = 0.5"));
+        assert_eq!(settings.fov, 90.);
+        assert_eq!(settings.smoothing, 0.5);
+    }
+}
@@ -0,0 +1,165 @@
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+
+use super::*;
+
+/// Furthest an image can be zoomed in, as a multiple of the size that fits the window
+const MAX_ZOOM: f32 = 12.;
+/// How much each line scrolled by the mouse wheel zooms the image
+const ZOOM_STEP: f32 = 1.2;
+
+/// Full-screen panel that an image is shown on by [view_image]
+#[derive(Component)]
+pub struct ImageViewer;
+
+/// Zoom and pan of the image shown in the [ImageViewer]
+#[derive(Component)]
+pub struct ViewedImage {
+    /// Multiple of the size that fits the window
+    zoom: f32,
+    /// Offset in pixels of the centre of the image from the centre of the window
+    offset: Vec2,
+}
+
+/// Open the image chosen by the player's [InteractableAction::ViewImage] full-screen, and pan it
+/// with the mouse and zoom it with the wheel until it is closed with the interact key or Escape
+pub(crate) fn view_image(
+    mut commands: Commands,
+    kb: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    mut mouse: EventReader<MouseMotion>,
+    mut wheel: EventReader<MouseWheel>,
+    mut players: Query<&mut Player>,
+    viewers: Query<Entity, With<ImageViewer>>,
+    mut viewed: Query<(&mut ViewedImage, &mut Style, &UiImage)>,
+    mut images: ResMut<Assets<Image>>,
+    windows: Res<Windows>,
+    asset_server: Res<AssetServer>,
+) {
+    let mut player = match players.get_single_mut() {
+        Ok(player) => player,
+        Err(_) => return,
+    };
+    let image = match &player.viewed_image {
+        Some(image) => image.clone(),
+        None => return,
+    };
+
+    // Opened this frame, so the key that opened it is still released
+    let viewer = match viewers.get_single() {
+        Ok(viewer) => viewer,
+        Err(_) => {
+            let hint = format!("[{}] Exit    Move the mouse to pan, scroll to zoom", key_name(settings.keys.interact));
+            // Textures without a high-resolution version are shown through a smoothed copy, which
+            // is freed with the viewer
+            let image = match images.get(&image) {
+                Some(texture) if texture.sampler_descriptor != image_sampler() => {
+                    let mut copy = texture.clone();
+                    copy.sampler_descriptor = image_sampler();
+                    images.add(copy)
+                },
+                _ => image,
+            };
+            spawn_viewer(&mut commands, image, hint, asset_server.get_handle("fonts/times-new-roman.ttf"));
+            return
+        },
+    };
+    if kb.just_released(settings.keys.interact) || kb.just_pressed(KeyCode::Escape) {
+        commands.entity(viewer).despawn_recursive();
+        player.viewed_image = None;
+        return
+    }
+
+    let window = match windows.get_primary() {
+        Some(window) => Vec2::new(window.width(), window.height()),
+        None => return,
+    };
+    for (mut view, mut style, ui_image) in viewed.iter_mut() {
+        let size = match images.get(&ui_image.0) {
+            Some(image) => image.size(),
+            None => continue,
+        };
+
+        for event in wheel.iter() {
+            let lines = match event.unit {
+                MouseScrollUnit::Line => event.y,
+                MouseScrollUnit::Pixel => event.y / 40.,
+            };
+            let zoom = (view.zoom * ZOOM_STEP.powf(lines)).clamp(1., MAX_ZOOM);
+            // Keep the point at the centre of the window in place
+            let scale = zoom / view.zoom;
+            view.offset *= scale;
+            view.zoom = zoom;
+        }
+        for event in mouse.iter() {
+            view.offset += event.delta;
+        }
+
+        // The image can't be panned past its edges, and is centred on any axis that fits
+        let size = size * (window / size).min_element() * view.zoom;
+        let slack = ((size - window) / 2.).max(Vec2::ZERO);
+        view.offset = view.offset.clamp(-slack, slack);
+        let corner = (window - size) / 2. + view.offset;
+
+        let layout = (
+            Size::new(Val::Px(size.x), Val::Px(size.y)),
+            Rect { left: Val::Px(corner.x), top: Val::Px(corner.y), ..default() },
+        );
+        if (style.size, style.position) != layout {
+            (style.size, style.position) = layout;
+        }
+    }
+}
+
+/// Spawn the full-screen panel of the image viewer, with the image hidden until [view_image] has
+/// sized it
+fn spawn_viewer(commands: &mut Commands, image: Handle<Image>, hint: String, font: Handle<Font>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                ..default()
+            },
+            color: UiColor(Color::BLACK),
+            ..default()
+        })
+        .insert(ImageViewer)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(ImageBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        size: Size::new(Val::Px(0.), Val::Px(0.)),
+                        ..default()
+                    },
+                    image: UiImage(image),
+                    ..default()
+                })
+                .insert(ViewedImage {
+                    zoom: 1.,
+                    offset: Vec2::ZERO,
+                });
+
+            parent.spawn_bundle(TextBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        top: Val::Percent(5.),
+                        left: Val::Percent(5.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                text: Text::with_section(
+                    hint,
+                    TextStyle {
+                        font,
+                        font_size: 24.,
+                        color: Color::WHITE,
+                    },
+                    default(),
+                ),
+                ..default()
+            });
+        });
+}